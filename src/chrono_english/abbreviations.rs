@@ -0,0 +1,191 @@
+use std::collections::BTreeMap;
+use std::sync::OnceLock;
+
+use chrono::prelude::{
+  DateTime, Datelike, FixedOffset, NaiveDate, NaiveDateTime, Offset, TimeZone,
+};
+use chrono_tz::{OffsetName, Tz, TZ_VARIANTS};
+
+use super::errors::{date_error, DateError, DateResult};
+
+/// A zone which uses an abbreviation, with the offset it stands for there
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AbbreviationUse {
+  pub zone: Tz,
+  pub offset: FixedOffset,
+}
+
+/// Every abbreviation of the IANA database (upper case),
+/// with the zones using it in alphabetical order.
+///
+/// Built once by looking at every zone at a NH-winter and NH-summer
+/// reference time, which covers both hemispheres and standard/DST pairs.
+pub fn abbreviations() -> &'static BTreeMap<String, Vec<AbbreviationUse>> {
+  static ABBREVIATIONS: OnceLock<BTreeMap<String, Vec<AbbreviationUse>>> =
+    OnceLock::new();
+
+  ABBREVIATIONS.get_or_init(|| {
+    let winter = DateTime::from_timestamp(1_577_836_800, 0)
+      .unwrap()
+      .naive_utc(); // 2020-01-01
+    let summer = DateTime::from_timestamp(1_593_561_600, 0)
+      .unwrap()
+      .naive_utc(); // 2020-07-01
+
+    let mut map: BTreeMap<String, Vec<AbbreviationUse>> = BTreeMap::new();
+    for &zone in TZ_VARIANTS.iter() {
+      for &dt in &[winter, summer] {
+        let offset = zone.offset_from_utc_datetime(&dt);
+        if let Some(abbrev) = offset.abbreviation() {
+          let usage = AbbreviationUse {
+            zone,
+            offset: offset.fix(),
+          };
+          let uses = map.entry(abbrev.to_ascii_uppercase()).or_default();
+          if !uses.contains(&usage) {
+            uses.push(usage);
+          }
+        }
+      }
+    }
+    map
+  })
+}
+
+/// Conventional meaning of abbreviations which stand for several offsets,
+/// when neither the zone of the user nor their preferences decide
+const CONVENTIONAL: [(&str, Tz); 4] = [
+  ("CDT", Tz::America__Chicago),
+  ("CST", Tz::America__Chicago),
+  ("IST", Tz::Asia__Kolkata),
+  ("PST", Tz::America__Los_Angeles),
+];
+
+/// The offset an abbreviation stands for at the local time `naive`,
+/// and why.
+///
+/// The first rule which applies decides:
+/// the zone of the user, their preferred zones,
+/// a single offset for all zones using the abbreviation,
+/// the zones in the region of the user (like `Asia`),
+/// and the conventional meaning, like India for `IST`.
+pub fn resolve_abbreviation(
+  abbrev: &str,
+  naive: NaiveDateTime,
+  zone: Option<Tz>,
+  preferred: &[Tz],
+) -> DateResult<(FixedOffset, String)> {
+  let abbrev = abbrev.to_ascii_uppercase();
+  let mut uses: Vec<AbbreviationUse> = Vec::new();
+  for usage in abbreviations().get(&abbrev).into_iter().flatten() {
+    if uses.iter().all(|known| known.zone != usage.zone) {
+      uses.push(*usage);
+    }
+  }
+  // zones which used the abbreviation around the date, with the offset
+  // it had then (e.g. MSK was +04:00 from 2011 to 2014)
+  let mut candidates: Vec<AbbreviationUse> = uses
+    .iter()
+    .filter_map(|usage| {
+      let offset = offset_at(usage.zone, &abbrev, naive)?;
+      Some(AbbreviationUse { offset, ..*usage })
+    })
+    .collect();
+  if candidates.is_empty() {
+    candidates = uses;
+  }
+  let find = |zone: Tz| candidates.iter().find(|usage| usage.zone == zone);
+
+  if let Some(usage) = zone.and_then(find) {
+    return Ok((usage.offset, format!("as in {}", usage.zone.name())));
+  }
+  if let Some(usage) = preferred.iter().find_map(|&zone| find(zone)) {
+    return Ok((
+      usage.offset,
+      format!("as in preferred {}", usage.zone.name()),
+    ));
+  }
+  let offsets = group_by_offset(&candidates);
+  if let [(offset, _)] = offsets[..] {
+    return Ok((offset, "the only offset it stands for".to_string()));
+  }
+  let region = zone.and_then(|zone| zone.name().split_once('/'));
+  if let Some((region, _)) = region {
+    let in_region: Vec<AbbreviationUse> = candidates
+      .iter()
+      .filter(|usage| usage.zone.name().starts_with(&format!("{region}/")))
+      .copied()
+      .collect();
+    match group_by_offset(&in_region)[..] {
+      [] => (),
+      [(offset, _)] => {
+        return Ok((offset, format!("as in the region {region}")));
+      }
+      ref offsets => return Err(ambiguous(&abbrev, offsets)),
+    }
+  }
+  if let Some(usage) = CONVENTIONAL
+    .iter()
+    .filter(|(name, _)| *name == abbrev)
+    .find_map(|&(_, zone)| find(zone))
+  {
+    return Ok((
+      usage.offset,
+      format!("as in {} by convention", usage.zone.name()),
+    ));
+  }
+  Err(ambiguous(&abbrev, &offsets))
+}
+
+// offset of the abbreviation in `zone` in the year of `naive`
+fn offset_at(
+  zone: Tz,
+  abbrev: &str,
+  naive: NaiveDateTime,
+) -> Option<FixedOffset> {
+  // standard and daylight saving time of that year
+  let mid_month = |month| {
+    NaiveDate::from_ymd_opt(naive.year(), month, 15)
+      .and_then(|date| date.and_hms_opt(12, 0, 0))
+  };
+  [Some(naive), mid_month(1), mid_month(7)]
+    .into_iter()
+    .flatten()
+    .map(|naive| zone.offset_from_utc_datetime(&naive))
+    .find(|offset| {
+      offset
+        .abbreviation()
+        .is_some_and(|name| name.eq_ignore_ascii_case(abbrev))
+    })
+    .map(|offset| offset.fix())
+}
+
+// offsets in order of first use, with the zones using each of them
+fn group_by_offset(uses: &[AbbreviationUse]) -> Vec<(FixedOffset, Vec<Tz>)> {
+  let mut groups: Vec<(FixedOffset, Vec<Tz>)> = Vec::new();
+  for usage in uses {
+    match groups
+      .iter_mut()
+      .find(|(offset, _)| *offset == usage.offset)
+    {
+      Some((_, zones)) => zones.push(usage.zone),
+      None => groups.push((usage.offset, vec![usage.zone])),
+    }
+  }
+  groups
+}
+
+fn ambiguous(abbrev: &str, offsets: &[(FixedOffset, Vec<Tz>)]) -> DateError {
+  let candidates: Vec<String> = offsets
+    .iter()
+    .map(|(offset, zones)| {
+      let names: Vec<&str> = zones.iter().map(|zone| zone.name()).collect();
+      format!("{offset} ({})", names.join(", "))
+    })
+    .collect();
+  date_error(format!(
+    "ambiguous zone abbreviation '{abbrev}', could be {}; \
+     set a preferred zone to decide",
+    candidates.join(" or ")
+  ))
+}
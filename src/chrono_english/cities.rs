@@ -0,0 +1,72 @@
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+use chrono_tz::{Tz, TZ_VARIANTS};
+
+/// Cities which are not part of an IANA zone name
+const CITIES: [(&str, Tz); 36] = [
+  ("atlanta", Tz::America__New_York),
+  ("austin", Tz::America__Chicago),
+  ("bangalore", Tz::Asia__Kolkata),
+  ("barcelona", Tz::Europe__Madrid),
+  ("beijing", Tz::Asia__Shanghai),
+  ("bengaluru", Tz::Asia__Kolkata),
+  ("boston", Tz::America__New_York),
+  ("chennai", Tz::Asia__Kolkata),
+  ("cologne", Tz::Europe__Berlin),
+  ("dallas", Tz::America__Chicago),
+  ("delhi", Tz::Asia__Kolkata),
+  ("denver", Tz::America__Denver),
+  ("frankfurt", Tz::Europe__Berlin),
+  ("geneva", Tz::Europe__Zurich),
+  ("guangzhou", Tz::Asia__Shanghai),
+  ("hamburg", Tz::Europe__Berlin),
+  ("houston", Tz::America__Chicago),
+  ("hyderabad", Tz::Asia__Kolkata),
+  ("las vegas", Tz::America__Los_Angeles),
+  ("lyon", Tz::Europe__Paris),
+  ("miami", Tz::America__New_York),
+  ("milan", Tz::Europe__Rome),
+  ("montreal", Tz::America__Toronto),
+  ("mumbai", Tz::Asia__Kolkata),
+  ("munich", Tz::Europe__Berlin),
+  ("new delhi", Tz::Asia__Kolkata),
+  ("nyc", Tz::America__New_York),
+  ("osaka", Tz::Asia__Tokyo),
+  ("philadelphia", Tz::America__New_York),
+  ("portland", Tz::America__Los_Angeles),
+  ("rio de janeiro", Tz::America__Sao_Paulo),
+  ("san francisco", Tz::America__Los_Angeles),
+  ("são paulo", Tz::America__Sao_Paulo),
+  ("seattle", Tz::America__Los_Angeles),
+  ("shenzhen", Tz::Asia__Shanghai),
+  ("washington", Tz::America__New_York),
+];
+
+/// Find a zone by its IANA name like `Europe/Paris`,
+/// or by a city like `Paris`, `New York` or `Munich`, ignoring case
+pub fn zone_named(name: &str) -> Option<Tz> {
+  static NAMES: OnceLock<HashMap<String, Tz>> = OnceLock::new();
+
+  let names = NAMES.get_or_init(|| {
+    let mut names: HashMap<String, Tz> = HashMap::new();
+    for &zone in TZ_VARIANTS.iter() {
+      names.insert(zone.name().to_lowercase(), zone);
+    }
+    // 'America/Argentina/Buenos_Aires' is in 'buenos aires'
+    for &zone in TZ_VARIANTS.iter() {
+      if let Some((_, city)) = zone.name().rsplit_once('/') {
+        names
+          .entry(city.replace('_', " ").to_lowercase())
+          .or_insert(zone);
+      }
+    }
+    for (city, zone) in CITIES {
+      names.entry(city.to_string()).or_insert(zone);
+    }
+    names
+  });
+
+  let name = name.split_whitespace().collect::<Vec<_>>().join(" ");
+  names.get(&name.to_lowercase()).copied()
+}
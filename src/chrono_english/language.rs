@@ -0,0 +1,969 @@
+use std::fmt;
+
+use super::types::{Direction, Interval};
+
+/// Parts of spelled-out numbers like 'a hundred and twenty', 'two and a half'
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum NumberWord {
+  Value(u32),    // 'one', 'twenty', 'couple', 'few'
+  Scale(u32),    // 'hundred', 'thousand', 'dozen'
+  Fraction(f64), // 'half', 'quarter'
+  Article,       // 'a', 'an'
+  And,           // 'and'
+  Of,            // 'of'
+}
+
+/// Grammatical words whose meaning doesn't depend on the language
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Keyword {
+  /// After an amount: '3 days ago'
+  Ago,
+  /// Before an amount: 'vor 3 Tagen', 'il y a 3 jours'
+  AgoBefore,
+  /// Before an amount: 'in 3 days', 'dans 3 jours'
+  In,
+  /// Before a time: 'at 5', 'um 5', 'à 5'
+  At,
+  /// Between minutes and hour: 'quarter past 5', 'Viertel nach 5'
+  Past,
+  /// Between minutes and hour: 'quarter to 5', 'Viertel vor 5'
+  To,
+  /// After an hour: 'cinq heures moins le quart'
+  Minus,
+  /// After an hour: '15 Uhr', '15h30'
+  Hour,
+  /// 'next week', 'nächste Woche'
+  Week,
+  /// 'start of week'
+  Start,
+  Of,
+  /// Articles and prepositions which are ignored: 'le', 'el', 'am'
+  Filler,
+}
+
+/// The vocabulary of a language.
+///
+/// All words are looked up in lowercase.
+/// The grammar itself is shared by all languages,
+/// so a language only has to provide its words.
+pub trait Language: fmt::Debug + Send + Sync {
+  /// ISO 639-1 code like `en`
+  fn code(&self) -> &'static str;
+
+  /// Day of the week, counted from Monday = 0
+  fn week_day(&self, word: &str) -> Option<u32>;
+
+  /// Month from January = 1
+  fn month_name(&self, word: &str) -> Option<u32>;
+
+  fn time_unit(&self, word: &str) -> Option<Interval>;
+
+  /// Days from today for words like 'tomorrow'
+  fn date_shortcut_offset(&self, word: &str) -> Option<i32>;
+
+  /// 'next', 'last' or 'this'
+  fn direction(&self, word: &str) -> Option<Direction>;
+
+  fn number_word(&self, word: &str) -> Option<NumberWord>;
+
+  fn is(&self, word: &str, keyword: Keyword) -> bool;
+
+  /// The English name of a time of day like 'mittag' (noon),
+  /// which is looked up in the configured times of day
+  fn time_of_day(&self, _word: &str) -> Option<&'static str> {
+    None
+  }
+
+  /// Phrases like 'il y a' or 'après-demain' which are read as one word,
+  /// with spaces and hyphens replaced by underscores
+  fn phrases(&self) -> &[&'static str] {
+    &[]
+  }
+
+  /// Whether numeric dates are written day first, so 9/11 is 9 November
+  fn day_first(&self) -> bool {
+    false
+  }
+
+  /// Words suggested when completing expressions in a shell,
+  /// like the names of days and months
+  fn vocabulary(&self) -> Vec<&'static str> {
+    Vec::new()
+  }
+
+  /// Whether a fraction before an hour counts towards that hour,
+  /// like 'halb drei' (2:30)
+  fn fraction_before_hour(&self) -> bool {
+    false
+  }
+
+  /// Rewrite an expression into the word order of the grammar:
+  /// phrases are joined, fillers dropped, and directions
+  /// following a name ('vendredi prochain') moved before it.
+  fn prepare(&self, input: &str) -> String {
+    let words: Vec<&str> = input.split_whitespace().collect();
+    let mut prepared: Vec<String> = Vec::new();
+    let mut i = 0;
+    while i < words.len() {
+      let phrase = self.phrases().iter().find(|phrase| {
+        let parts: Vec<&str> = phrase.split(' ').collect();
+        parts.len() <= words.len() - i
+          && parts
+            .iter()
+            .zip(&words[i..])
+            .all(|(part, word)| word.to_lowercase() == *part)
+      });
+      let word = match phrase {
+        Some(phrase) => {
+          i += phrase.split(' ').count();
+          phrase.replace([' ', '-', '\''], "_")
+        }
+        None => {
+          i += 1;
+          words[i - 1].to_string()
+        }
+      };
+      let lower = word.to_lowercase();
+      if self.is(&lower, Keyword::Filler) {
+        continue;
+      }
+      if self.direction(&lower).is_some() {
+        let follows_name = prepared.last().is_some_and(|last| {
+          let last = last.to_lowercase();
+          self.week_day(&last).is_some()
+            || self.month_name(&last).is_some()
+            || self.is(&last, Keyword::Week)
+        });
+        if follows_name {
+          let name = prepared.pop().unwrap();
+          prepared.push(word);
+          prepared.push(name);
+          continue;
+        }
+      }
+      prepared.push(word);
+    }
+    prepared.join(" ")
+  }
+}
+
+/// English, which matches month names by their first three letters
+#[derive(Debug)]
+pub struct English;
+
+pub static ENGLISH: English = English;
+
+impl Language for English {
+  fn code(&self) -> &'static str {
+    "en"
+  }
+
+  // same as chrono's 'count days from monday' convention,
+  // the first three or more letters of a name, so 'month' is not Monday
+  fn week_day(&self, s: &str) -> Option<u32> {
+    const NAMES: [&str; 7] = [
+      "monday",
+      "tuesday",
+      "wednesday",
+      "thursday",
+      "friday",
+      "saturday",
+      "sunday",
+    ];
+    if s.len() < 3 {
+      return None;
+    }
+    let day = NAMES.iter().position(|name| name.starts_with(s))?;
+    Some(day as u32)
+  }
+
+  fn month_name(&self, s: &str) -> Option<u32> {
+    if s.len() < 3 {
+      return None;
+    }
+    Some(match s.get(0..3)? {
+      "jan" => 1,
+      "feb" => 2,
+      "mar" => 3,
+      "apr" => 4,
+      "may" => 5,
+      "jun" => 6,
+      "jul" => 7,
+      "aug" => 8,
+      "sep" => 9,
+      "oct" => 10,
+      "nov" => 11,
+      "dec" => 12,
+      _ => return None,
+    })
+  }
+
+  fn time_unit(&self, s: &str) -> Option<Interval> {
+    use Interval::*;
+    let name = if s.len() < 3 {
+      match s.get(0..1)? {
+        "s" => "sec",
+        "m" => "min",
+        "h" => "hou",
+        "w" => "wee",
+        "d" => "day",
+        "y" => "yea",
+        _ => return None,
+      }
+    }
+    else {
+      s.get(0..3)?
+    };
+    Some(match name {
+      "sec" => Seconds(1),
+      "min" => Seconds(60),
+      "hou" => Seconds(60 * 60),
+      "day" => Days(1),
+      "wee" => Days(7),
+      "mon" => Months(1),
+      "yea" => Months(12),
+      _ => return None,
+    })
+  }
+
+  fn date_shortcut_offset(&self, s: &str) -> Option<i32> {
+    match s {
+      "now" => Some(0),
+      "today" | "tdy" | "tonight" => Some(0),
+      "yesterday" | "yday" | "ytd" => Some(-1),
+      "tomorrow" | "tmr" | "tmrw" => Some(1),
+      _ => None,
+    }
+  }
+
+  fn direction(&self, s: &str) -> Option<Direction> {
+    use Direction::*;
+    match s {
+      "next" => Some(Next),
+      "last" => Some(Last),
+      "this" => Some(This),
+      _ => None,
+    }
+  }
+
+  fn number_word(&self, s: &str) -> Option<NumberWord> {
+    use NumberWord::*;
+    Some(match s {
+      "zero" => Value(0),
+      "one" => Value(1),
+      "two" | "couple" => Value(2),
+      "three" | "few" => Value(3),
+      "four" => Value(4),
+      "five" => Value(5),
+      "six" => Value(6),
+      "seven" => Value(7),
+      "eight" => Value(8),
+      "nine" => Value(9),
+      "ten" => Value(10),
+      "eleven" => Value(11),
+      "twelve" => Value(12),
+      "thirteen" => Value(13),
+      "fourteen" => Value(14),
+      "fifteen" => Value(15),
+      "sixteen" => Value(16),
+      "seventeen" => Value(17),
+      "eighteen" => Value(18),
+      "nineteen" => Value(19),
+      "twenty" => Value(20),
+      "thirty" => Value(30),
+      "forty" => Value(40),
+      "fifty" => Value(50),
+      "sixty" => Value(60),
+      "seventy" => Value(70),
+      "eighty" => Value(80),
+      "ninety" => Value(90),
+      "hundred" => Scale(100),
+      "thousand" => Scale(1000),
+      "dozen" => Scale(12),
+      "half" => Fraction(0.5),
+      "quarter" | "quarters" => Fraction(0.25),
+      "a" | "an" => Article,
+      "and" => And,
+      "of" => Of,
+      _ => return None,
+    })
+  }
+
+  fn is(&self, s: &str, keyword: Keyword) -> bool {
+    use Keyword::*;
+    match keyword {
+      Ago => s == "ago",
+      In => s == "in",
+      At => s == "at",
+      Past => s == "past" || s == "after",
+      To => s == "to" || s == "before",
+      Hour => s == "o_clock",
+      Week => s == "week",
+      Start => s == "start",
+      Of => s == "of",
+      Filler => s == "the",
+      AgoBefore | Minus => false,
+    }
+  }
+
+  fn phrases(&self) -> &[&'static str] {
+    &["o'clock"]
+  }
+
+  fn vocabulary(&self) -> Vec<&'static str> {
+    vec![
+      "monday",
+      "tuesday",
+      "wednesday",
+      "thursday",
+      "friday",
+      "saturday",
+      "sunday",
+      "january",
+      "february",
+      "march",
+      "april",
+      "may",
+      "june",
+      "july",
+      "august",
+      "september",
+      "october",
+      "november",
+      "december",
+      "now",
+      "today",
+      "tonight",
+      "tomorrow",
+      "yesterday",
+      "next",
+      "last",
+      "this",
+      "ago",
+      "in",
+      "at",
+      "noon",
+      "midnight",
+      "morning",
+      "afternoon",
+      "evening",
+      "night",
+      "seconds",
+      "minutes",
+      "hours",
+      "days",
+      "weeks",
+      "months",
+      "years",
+      "week",
+      "start",
+      "past",
+      "to",
+    ]
+  }
+}
+
+/// A language defined by word lists
+#[derive(Debug)]
+pub struct Pack {
+  pub code: &'static str,
+  pub day_first: bool,
+  pub fraction_before_hour: bool,
+  pub phrases: &'static [&'static str],
+  pub week_days: &'static [(&'static str, u32)],
+  pub months: &'static [(&'static str, u32)],
+  pub units: &'static [(&'static str, Interval)],
+  pub shortcuts: &'static [(&'static str, i32)],
+  pub directions: &'static [(&'static str, Direction)],
+  pub numbers: &'static [(&'static str, NumberWord)],
+  pub keywords: &'static [(&'static str, Keyword)],
+  /// Names of times of day with their English equivalent
+  pub times_of_day: &'static [(&'static str, &'static str)],
+}
+
+fn lookup<T: Copy>(table: &[(&str, T)], word: &str) -> Option<T> {
+  table
+    .iter()
+    .find(|(name, _)| *name == word)
+    .map(|&(_, value)| value)
+}
+
+impl Language for Pack {
+  fn code(&self) -> &'static str {
+    self.code
+  }
+
+  fn week_day(&self, word: &str) -> Option<u32> {
+    lookup(self.week_days, word)
+  }
+
+  fn month_name(&self, word: &str) -> Option<u32> {
+    lookup(self.months, word)
+  }
+
+  fn time_unit(&self, word: &str) -> Option<Interval> {
+    lookup(self.units, word)
+  }
+
+  fn date_shortcut_offset(&self, word: &str) -> Option<i32> {
+    lookup(self.shortcuts, word)
+  }
+
+  fn direction(&self, word: &str) -> Option<Direction> {
+    lookup(self.directions, word)
+  }
+
+  fn number_word(&self, word: &str) -> Option<NumberWord> {
+    lookup(self.numbers, word)
+  }
+
+  fn is(&self, word: &str, keyword: Keyword) -> bool {
+    self
+      .keywords
+      .iter()
+      .any(|&(name, kw)| name == word && kw == keyword)
+  }
+
+  fn time_of_day(&self, word: &str) -> Option<&'static str> {
+    lookup(self.times_of_day, word)
+  }
+
+  fn phrases(&self) -> &[&'static str] {
+    self.phrases
+  }
+
+  fn day_first(&self) -> bool {
+    self.day_first
+  }
+
+  fn fraction_before_hour(&self) -> bool {
+    self.fraction_before_hour
+  }
+
+  // full words only, phrases are typed with spaces
+  fn vocabulary(&self) -> Vec<&'static str> {
+    let keywords = self
+      .keywords
+      .iter()
+      .filter(|(_, keyword)| *keyword != Keyword::Filler)
+      .map(|&(word, _)| word);
+    let mut words: Vec<&'static str> = Vec::new();
+    for word in (self.week_days.iter().map(|&(word, _)| word))
+      .chain(self.months.iter().map(|&(word, _)| word))
+      .chain(self.shortcuts.iter().map(|&(word, _)| word))
+      .chain(self.directions.iter().map(|&(word, _)| word))
+      .chain(keywords)
+      .chain(self.times_of_day.iter().map(|&(word, _)| word))
+      .chain(self.units.iter().map(|&(word, _)| word))
+    {
+      if word.chars().count() > 2
+        && !word.contains('_')
+        && !words.contains(&word)
+      {
+        words.push(word);
+      }
+    }
+    words
+  }
+}
+
+const SECOND: Interval = Interval::Seconds(1);
+const MINUTE: Interval = Interval::Seconds(60);
+const HOUR: Interval = Interval::Seconds(60 * 60);
+const DAY: Interval = Interval::Days(1);
+const WEEK: Interval = Interval::Days(7);
+const MONTH: Interval = Interval::Months(1);
+const YEAR: Interval = Interval::Months(12);
+
+pub static GERMAN: Pack = Pack {
+  code: "de",
+  day_first: true,
+  fraction_before_hour: true,
+  phrases: &[],
+  week_days: &[
+    ("montag", 0),
+    ("mo", 0),
+    ("dienstag", 1),
+    ("di", 1),
+    ("mittwoch", 2),
+    ("mi", 2),
+    ("donnerstag", 3),
+    ("do", 3),
+    ("freitag", 4),
+    ("fr", 4),
+    ("samstag", 5),
+    ("sonnabend", 5),
+    ("sa", 5),
+    ("sonntag", 6),
+    ("so", 6),
+  ],
+  months: &[
+    ("januar", 1),
+    ("jänner", 1),
+    ("jan", 1),
+    ("februar", 2),
+    ("feb", 2),
+    ("märz", 3),
+    ("maerz", 3),
+    ("mär", 3),
+    ("april", 4),
+    ("apr", 4),
+    ("mai", 5),
+    ("juni", 6),
+    ("jun", 6),
+    ("juli", 7),
+    ("jul", 7),
+    ("august", 8),
+    ("aug", 8),
+    ("september", 9),
+    ("sep", 9),
+    ("sept", 9),
+    ("oktober", 10),
+    ("okt", 10),
+    ("november", 11),
+    ("nov", 11),
+    ("dezember", 12),
+    ("dez", 12),
+  ],
+  units: &[
+    ("sekunde", SECOND),
+    ("sekunden", SECOND),
+    ("sek", SECOND),
+    ("minute", MINUTE),
+    ("minuten", MINUTE),
+    ("min", MINUTE),
+    ("stunde", HOUR),
+    ("stunden", HOUR),
+    ("std", HOUR),
+    ("tag", DAY),
+    ("tage", DAY),
+    ("tagen", DAY),
+    ("woche", WEEK),
+    ("wochen", WEEK),
+    ("monat", MONTH),
+    ("monate", MONTH),
+    ("monaten", MONTH),
+    ("jahr", YEAR),
+    ("jahre", YEAR),
+    ("jahren", YEAR),
+  ],
+  shortcuts: &[
+    ("jetzt", 0),
+    ("heute", 0),
+    ("morgen", 1),
+    ("übermorgen", 2),
+    ("uebermorgen", 2),
+    ("gestern", -1),
+    ("vorgestern", -2),
+  ],
+  directions: &[
+    ("nächste", Direction::Next),
+    ("nächsten", Direction::Next),
+    ("nächster", Direction::Next),
+    ("nächstes", Direction::Next),
+    ("naechste", Direction::Next),
+    ("naechsten", Direction::Next),
+    ("kommende", Direction::Next),
+    ("kommenden", Direction::Next),
+    ("letzte", Direction::Last),
+    ("letzten", Direction::Last),
+    ("letzter", Direction::Last),
+    ("letztes", Direction::Last),
+    ("vorige", Direction::Last),
+    ("vorigen", Direction::Last),
+    ("diese", Direction::This),
+    ("diesen", Direction::This),
+    ("dieser", Direction::This),
+    ("dieses", Direction::This),
+  ],
+  numbers: &[
+    ("null", NumberWord::Value(0)),
+    ("eins", NumberWord::Value(1)),
+    ("zwei", NumberWord::Value(2)),
+    ("paar", NumberWord::Value(2)),
+    ("drei", NumberWord::Value(3)),
+    ("vier", NumberWord::Value(4)),
+    ("fünf", NumberWord::Value(5)),
+    ("sechs", NumberWord::Value(6)),
+    ("sieben", NumberWord::Value(7)),
+    ("acht", NumberWord::Value(8)),
+    ("neun", NumberWord::Value(9)),
+    ("zehn", NumberWord::Value(10)),
+    ("elf", NumberWord::Value(11)),
+    ("zwölf", NumberWord::Value(12)),
+    ("dreizehn", NumberWord::Value(13)),
+    ("vierzehn", NumberWord::Value(14)),
+    ("fünfzehn", NumberWord::Value(15)),
+    ("sechzehn", NumberWord::Value(16)),
+    ("siebzehn", NumberWord::Value(17)),
+    ("achtzehn", NumberWord::Value(18)),
+    ("neunzehn", NumberWord::Value(19)),
+    ("zwanzig", NumberWord::Value(20)),
+    ("dreißig", NumberWord::Value(30)),
+    ("vierzig", NumberWord::Value(40)),
+    ("fünfzig", NumberWord::Value(50)),
+    ("sechzig", NumberWord::Value(60)),
+    ("siebzig", NumberWord::Value(70)),
+    ("achtzig", NumberWord::Value(80)),
+    ("neunzig", NumberWord::Value(90)),
+    ("hundert", NumberWord::Scale(100)),
+    ("tausend", NumberWord::Scale(1000)),
+    ("dutzend", NumberWord::Scale(12)),
+    ("halb", NumberWord::Fraction(0.5)),
+    ("halbe", NumberWord::Fraction(0.5)),
+    ("halben", NumberWord::Fraction(0.5)),
+    ("viertel", NumberWord::Fraction(0.25)),
+    ("dreiviertel", NumberWord::Fraction(0.75)),
+    ("anderthalb", NumberWord::Fraction(1.5)),
+    ("ein", NumberWord::Value(1)),
+    ("eine", NumberWord::Value(1)),
+    ("einen", NumberWord::Value(1)),
+    ("einer", NumberWord::Value(1)),
+    ("einem", NumberWord::Value(1)),
+    ("und", NumberWord::And),
+  ],
+  keywords: &[
+    ("vor", Keyword::AgoBefore),
+    ("in", Keyword::In),
+    ("um", Keyword::At),
+    ("nach", Keyword::Past),
+    ("vor", Keyword::To),
+    ("uhr", Keyword::Hour),
+    ("woche", Keyword::Week),
+    ("anfang", Keyword::Start),
+    ("am", Keyword::Filler),
+    ("den", Keyword::Filler),
+    ("der", Keyword::Filler),
+    ("die", Keyword::Filler),
+    ("im", Keyword::Filler),
+  ],
+  times_of_day: &[
+    ("mitternacht", "midnight"),
+    ("früh", "morning"),
+    ("morgens", "morning"),
+    ("vormittag", "morning"),
+    ("mittag", "noon"),
+    ("mittags", "noon"),
+    ("nachmittag", "afternoon"),
+    ("nachmittags", "afternoon"),
+    ("feierabend", "eod"),
+    ("abend", "evening"),
+    ("abends", "evening"),
+    ("nacht", "night"),
+    ("nachts", "night"),
+  ],
+};
+
+pub static FRENCH: Pack = Pack {
+  code: "fr",
+  day_first: true,
+  fraction_before_hour: false,
+  phrases: &[
+    "il y a",
+    "aujourd'hui",
+    "d'heure",
+    "après-demain",
+    "apres-demain",
+    "avant-hier",
+    "après-midi",
+    "apres-midi",
+  ],
+  week_days: &[
+    ("lundi", 0),
+    ("lun", 0),
+    ("mardi", 1),
+    ("mar", 1),
+    ("mercredi", 2),
+    ("mer", 2),
+    ("jeudi", 3),
+    ("jeu", 3),
+    ("vendredi", 4),
+    ("ven", 4),
+    ("samedi", 5),
+    ("sam", 5),
+    ("dimanche", 6),
+    ("dim", 6),
+  ],
+  months: &[
+    ("janvier", 1),
+    ("janv", 1),
+    ("février", 2),
+    ("fevrier", 2),
+    ("févr", 2),
+    ("mars", 3),
+    ("avril", 4),
+    ("avr", 4),
+    ("mai", 5),
+    ("juin", 6),
+    ("juillet", 7),
+    ("juil", 7),
+    ("août", 8),
+    ("aout", 8),
+    ("septembre", 9),
+    ("sept", 9),
+    ("octobre", 10),
+    ("oct", 10),
+    ("novembre", 11),
+    ("nov", 11),
+    ("décembre", 12),
+    ("decembre", 12),
+    ("déc", 12),
+  ],
+  units: &[
+    ("seconde", SECOND),
+    ("secondes", SECOND),
+    ("minute", MINUTE),
+    ("minutes", MINUTE),
+    ("heure", HOUR),
+    ("heures", HOUR),
+    ("d_heure", HOUR),
+    ("jour", DAY),
+    ("jours", DAY),
+    ("semaine", WEEK),
+    ("semaines", WEEK),
+    ("mois", MONTH),
+    ("an", YEAR),
+    ("ans", YEAR),
+    ("année", YEAR),
+    ("années", YEAR),
+  ],
+  shortcuts: &[
+    ("maintenant", 0),
+    ("aujourd_hui", 0),
+    ("demain", 1),
+    ("après_demain", 2),
+    ("apres_demain", 2),
+    ("hier", -1),
+    ("avant_hier", -2),
+  ],
+  directions: &[
+    ("prochain", Direction::Next),
+    ("prochaine", Direction::Next),
+    ("dernier", Direction::Last),
+    ("dernière", Direction::Last),
+    ("derniere", Direction::Last),
+    ("passé", Direction::Last),
+    ("passée", Direction::Last),
+    ("ce", Direction::This),
+    ("cet", Direction::This),
+    ("cette", Direction::This),
+  ],
+  numbers: &[
+    ("zéro", NumberWord::Value(0)),
+    ("deux", NumberWord::Value(2)),
+    ("trois", NumberWord::Value(3)),
+    ("quatre", NumberWord::Value(4)),
+    ("cinq", NumberWord::Value(5)),
+    ("six", NumberWord::Value(6)),
+    ("sept", NumberWord::Value(7)),
+    ("huit", NumberWord::Value(8)),
+    ("neuf", NumberWord::Value(9)),
+    ("dix", NumberWord::Value(10)),
+    ("onze", NumberWord::Value(11)),
+    ("douze", NumberWord::Value(12)),
+    ("treize", NumberWord::Value(13)),
+    ("quatorze", NumberWord::Value(14)),
+    ("quinze", NumberWord::Value(15)),
+    ("seize", NumberWord::Value(16)),
+    ("vingt", NumberWord::Value(20)),
+    ("trente", NumberWord::Value(30)),
+    ("quarante", NumberWord::Value(40)),
+    ("cinquante", NumberWord::Value(50)),
+    ("soixante", NumberWord::Value(60)),
+    ("cent", NumberWord::Scale(100)),
+    ("mille", NumberWord::Scale(1000)),
+    ("douzaine", NumberWord::Scale(12)),
+    ("demi", NumberWord::Fraction(0.5)),
+    ("demie", NumberWord::Fraction(0.5)),
+    ("quart", NumberWord::Fraction(0.25)),
+    ("quarts", NumberWord::Fraction(0.25)),
+    ("un", NumberWord::Value(1)),
+    ("une", NumberWord::Value(1)),
+    ("et", NumberWord::And),
+  ],
+  keywords: &[
+    ("il_y_a", Keyword::AgoBefore),
+    ("dans", Keyword::In),
+    ("à", Keyword::At),
+    ("a", Keyword::At),
+    ("vers", Keyword::At),
+    ("moins", Keyword::Minus),
+    ("h", Keyword::Hour),
+    ("heure", Keyword::Hour),
+    ("heures", Keyword::Hour),
+    ("semaine", Keyword::Week),
+    ("début", Keyword::Start),
+    ("le", Keyword::Filler),
+    ("la", Keyword::Filler),
+    ("les", Keyword::Filler),
+    ("de", Keyword::Filler),
+    ("du", Keyword::Filler),
+  ],
+  times_of_day: &[
+    ("minuit", "midnight"),
+    ("matin", "morning"),
+    ("midi", "noon"),
+    ("après_midi", "afternoon"),
+    ("apres_midi", "afternoon"),
+    ("soir", "evening"),
+    ("nuit", "night"),
+  ],
+};
+
+pub static SPANISH: Pack = Pack {
+  code: "es",
+  day_first: true,
+  fraction_before_hour: false,
+  phrases: &[
+    "pasado mañana",
+    "pasado manana",
+    "dentro de",
+    "que viene",
+    "a las",
+    "a la",
+    "por la mañana",
+    "por la manana",
+    "por la tarde",
+    "por la noche",
+  ],
+  week_days: &[
+    ("lunes", 0),
+    ("martes", 1),
+    ("miércoles", 2),
+    ("miercoles", 2),
+    ("jueves", 3),
+    ("viernes", 4),
+    ("sábado", 5),
+    ("sabado", 5),
+    ("domingo", 6),
+  ],
+  months: &[
+    ("enero", 1),
+    ("febrero", 2),
+    ("marzo", 3),
+    ("abril", 4),
+    ("mayo", 5),
+    ("junio", 6),
+    ("julio", 7),
+    ("agosto", 8),
+    ("septiembre", 9),
+    ("setiembre", 9),
+    ("octubre", 10),
+    ("noviembre", 11),
+    ("diciembre", 12),
+  ],
+  units: &[
+    ("segundo", SECOND),
+    ("segundos", SECOND),
+    ("minuto", MINUTE),
+    ("minutos", MINUTE),
+    ("hora", HOUR),
+    ("horas", HOUR),
+    ("día", DAY),
+    ("días", DAY),
+    ("dia", DAY),
+    ("dias", DAY),
+    ("semana", WEEK),
+    ("semanas", WEEK),
+    ("mes", MONTH),
+    ("meses", MONTH),
+    ("año", YEAR),
+    ("años", YEAR),
+  ],
+  shortcuts: &[
+    ("ahora", 0),
+    ("hoy", 0),
+    ("mañana", 1),
+    ("manana", 1),
+    ("pasado_mañana", 2),
+    ("pasado_manana", 2),
+    ("ayer", -1),
+    ("anteayer", -2),
+  ],
+  directions: &[
+    ("próximo", Direction::Next),
+    ("próxima", Direction::Next),
+    ("proximo", Direction::Next),
+    ("proxima", Direction::Next),
+    ("que_viene", Direction::Next),
+    ("pasado", Direction::Last),
+    ("pasada", Direction::Last),
+    ("último", Direction::Last),
+    ("última", Direction::Last),
+    ("este", Direction::This),
+    ("esta", Direction::This),
+  ],
+  numbers: &[
+    ("cero", NumberWord::Value(0)),
+    ("uno", NumberWord::Value(1)),
+    ("dos", NumberWord::Value(2)),
+    ("tres", NumberWord::Value(3)),
+    ("cuatro", NumberWord::Value(4)),
+    ("cinco", NumberWord::Value(5)),
+    ("seis", NumberWord::Value(6)),
+    ("siete", NumberWord::Value(7)),
+    ("ocho", NumberWord::Value(8)),
+    ("nueve", NumberWord::Value(9)),
+    ("diez", NumberWord::Value(10)),
+    ("once", NumberWord::Value(11)),
+    ("doce", NumberWord::Value(12)),
+    ("trece", NumberWord::Value(13)),
+    ("catorce", NumberWord::Value(14)),
+    ("quince", NumberWord::Value(15)),
+    ("veinte", NumberWord::Value(20)),
+    ("treinta", NumberWord::Value(30)),
+    ("cuarenta", NumberWord::Value(40)),
+    ("cincuenta", NumberWord::Value(50)),
+    ("sesenta", NumberWord::Value(60)),
+    ("cien", NumberWord::Scale(100)),
+    ("mil", NumberWord::Scale(1000)),
+    ("docena", NumberWord::Scale(12)),
+    ("medio", NumberWord::Fraction(0.5)),
+    ("media", NumberWord::Fraction(0.5)),
+    ("cuarto", NumberWord::Fraction(0.25)),
+    ("cuartos", NumberWord::Fraction(0.25)),
+    ("un", NumberWord::Value(1)),
+    ("una", NumberWord::Value(1)),
+    ("y", NumberWord::And),
+  ],
+  keywords: &[
+    ("hace", Keyword::AgoBefore),
+    ("en", Keyword::In),
+    ("dentro_de", Keyword::In),
+    ("a", Keyword::At),
+    ("a_las", Keyword::At),
+    ("a_la", Keyword::At),
+    ("menos", Keyword::Minus),
+    ("h", Keyword::Hour),
+    ("semana", Keyword::Week),
+    ("el", Keyword::Filler),
+    ("la", Keyword::Filler),
+    ("las", Keyword::Filler),
+    ("los", Keyword::Filler),
+    ("de", Keyword::Filler),
+    ("del", Keyword::Filler),
+  ],
+  times_of_day: &[
+    ("medianoche", "midnight"),
+    ("por_la_mañana", "morning"),
+    ("por_la_manana", "morning"),
+    ("mediodía", "noon"),
+    ("mediodia", "noon"),
+    ("tarde", "afternoon"),
+    ("por_la_tarde", "afternoon"),
+    ("noche", "night"),
+    ("por_la_noche", "night"),
+  ],
+};
+
+/// All shipped languages
+pub static LANGUAGES: [&dyn Language; 4] =
+  [&ENGLISH, &GERMAN, &FRENCH, &SPANISH];
+
+/// Look up a shipped language by its ISO 639-1 code
+pub fn by_code(code: &str) -> Option<&'static dyn Language> {
+  LANGUAGES
+    .iter()
+    .find(|language| language.code().eq_ignore_ascii_case(code))
+    .copied()
+}
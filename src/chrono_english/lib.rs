@@ -2,8 +2,11 @@
 #![allow(dead_code)]
 
 use chrono::prelude::*;
+use chrono::Duration;
 
+use super::abbreviations::resolve_abbreviation;
 pub use super::errors::*;
+use super::language::{Language, ENGLISH};
 use super::parser;
 use super::types::*;
 
@@ -11,43 +14,402 @@ use super::types::*;
 // pub use errors::{DateError, DateResult};
 // pub use types::Interval;
 
+/// Whether `9/11` means September 11 (US) or 9 November (UK)
 #[derive(Debug, Hash, Clone, Copy, Eq, PartialEq, Ord, PartialOrd)]
-
 pub enum Dialect {
   Uk,
   Us,
 }
 
+impl std::str::FromStr for Dialect {
+  type Err = DateError;
+
+  fn from_str(s: &str) -> DateResult<Dialect> {
+    match s.to_lowercase().as_str() {
+      "us" => Ok(Dialect::Us),
+      "uk" => Ok(Dialect::Uk),
+      _ => date_result("expected 'us' or 'uk'"),
+    }
+  }
+}
+
+/// The first day of a week.
+///
+/// Weeks starting on Monday are numbered according to ISO 8601
+/// (week 1 contains January 4), all others count the week
+/// containing January 1 as week 1.
+#[derive(Debug, Hash, Clone, Copy, Eq, PartialEq, Ord, PartialOrd)]
+pub enum WeekStart {
+  Monday,
+  Sunday,
+  Saturday,
+}
+
+impl WeekStart {
+  pub fn weekday(self) -> Weekday {
+    match self {
+      WeekStart::Monday => Weekday::Mon,
+      WeekStart::Sunday => Weekday::Sun,
+      WeekStart::Saturday => Weekday::Sat,
+    }
+  }
+
+  /// Number of days from the start of the week to `day`
+  pub fn days_into_week(self, day: Weekday) -> i64 {
+    let start = self.weekday().num_days_from_monday();
+    ((day.num_days_from_monday() + 7 - start) % 7) as i64
+  }
+
+  /// The first day of week `week` of `year`
+  pub fn week_start_date(self, year: i32, week: u32) -> Option<NaiveDate> {
+    if week == 0 || week > 53 {
+      return None;
+    }
+    if self == WeekStart::Monday {
+      return NaiveDate::from_isoywd_opt(year, week, Weekday::Mon);
+    }
+    let jan_1 = NaiveDate::from_ymd_opt(year, 1, 1)?;
+    let first = jan_1 - Duration::days(self.days_into_week(jan_1.weekday()));
+    let start = first + Duration::weeks(week as i64 - 1);
+    // A week belongs to the year of its last day
+    if (start + Duration::days(6)).year() != year {
+      return None;
+    }
+    Some(start)
+  }
+
+  /// Year and number of the week containing `date`
+  pub fn week_number(self, date: NaiveDate) -> (i32, u32) {
+    if self == WeekStart::Monday {
+      let week = date.iso_week();
+      return (week.year(), week.week());
+    }
+    let start = date - Duration::days(self.days_into_week(date.weekday()));
+    // The week belongs to the year of its last day
+    let end = start + Duration::days(6);
+    let year = end.year();
+    let first = self.week_start_date(year, 1).unwrap_or(start);
+    (year, ((start - first).num_days() / 7 + 1) as u32)
+  }
+}
+
+impl std::str::FromStr for WeekStart {
+  type Err = DateError;
+
+  fn from_str(s: &str) -> DateResult<WeekStart> {
+    match s.to_lowercase().as_str() {
+      "monday" | "mon" => Ok(WeekStart::Monday),
+      "sunday" | "sun" => Ok(WeekStart::Sunday),
+      "saturday" | "sat" => Ok(WeekStart::Saturday),
+      _ => date_result("expected 'monday', 'sunday' or 'saturday'"),
+    }
+  }
+}
+
+/// Meaning of an explicit "next", "this" or "last"
+/// before a weekday, a month or a day of a month
+#[derive(Debug, Hash, Clone, Copy, Eq, PartialEq, Ord, PartialOrd)]
+pub enum DirectionPolicy {
+  /// The closest match: "next friday" is the first Friday from now on,
+  /// "last march" the most recent March
+  Closest,
+  /// A match in the following, current or previous week or year:
+  /// "next friday" is the Friday of next week,
+  /// "next march" the March of next year
+  Period,
+}
+
+impl std::str::FromStr for DirectionPolicy {
+  type Err = DateError;
+
+  fn from_str(s: &str) -> DateResult<DirectionPolicy> {
+    match s {
+      "closest" => Ok(DirectionPolicy::Closest),
+      "period" => Ok(DirectionPolicy::Period),
+      _ => date_result("expected 'closest' or 'period'"),
+    }
+  }
+}
+
+/// Which occurrence an expression without "next", "last" or a year
+/// stands for, like "friday", "march", "5 may" or "Oct 10 13:55:36"
+#[derive(Debug, Hash, Clone, Copy, Eq, PartialEq, Ord, PartialOrd)]
+pub enum Bias {
+  /// The most recent one, including the current day or month
+  Past,
+  /// The first one from now on, including the current day or month
+  Future,
+  /// The closest one, the future one for ties
+  Nearest,
+}
+
+impl Bias {
+  /// The index of the preferred one of `periods` from `start` up to `end`,
+  /// which are in order and surround `now`
+  pub fn choose<Tz: TimeZone>(
+    self,
+    periods: &[(DateTime<Tz>, DateTime<Tz>)],
+    now: &DateTime<Tz>,
+  ) -> Option<usize> {
+    match self {
+      Bias::Past => periods.iter().rposition(|(start, _)| start <= now),
+      Bias::Future => periods.iter().position(|(_, end)| end > now),
+      Bias::Nearest => {
+        let distance = |(start, end): &(DateTime<Tz>, DateTime<Tz>)| {
+          if start > now {
+            start.clone() - now.clone()
+          }
+          else if end <= now {
+            now.clone() - end.clone()
+          }
+          else {
+            Duration::zero()
+          }
+        };
+        (0..periods.len())
+          .min_by_key(|&i| (distance(&periods[i]), periods[i].0 <= *now))
+      }
+    }
+  }
+}
+
+impl std::str::FromStr for Bias {
+  type Err = DateError;
+
+  fn from_str(s: &str) -> DateResult<Bias> {
+    match s {
+      "past" => Ok(Bias::Past),
+      "future" => Ok(Bias::Future),
+      "nearest" => Ok(Bias::Nearest),
+      _ => date_result("expected 'past', 'future' or 'nearest'"),
+    }
+  }
+}
+
+/// Time of day of an expression with a date, but no time,
+/// like "tomorrow", "friday", "1 month" or "2024-05-01"
+#[derive(Debug, Hash, Clone, Copy, Eq, PartialEq, Ord, PartialOrd)]
+pub enum TimePolicy {
+  /// The time of the reference time: "friday" is Friday at this time
+  Preserve,
+  /// Midnight: "tomorrow" is when tomorrow starts
+  StartOfDay,
+}
+
+impl std::str::FromStr for TimePolicy {
+  type Err = DateError;
+
+  fn from_str(s: &str) -> DateResult<TimePolicy> {
+    match s {
+      "preserve" => Ok(TimePolicy::Preserve),
+      "start-of-day" => Ok(TimePolicy::StartOfDay),
+      _ => date_result("expected 'preserve' or 'start-of-day'"),
+    }
+  }
+}
+
+/// How to resolve a local time which a daylight saving transition
+/// skips (e.g. 02:30 on a spring-forward day) or repeats (fall-back day)
+#[derive(Debug, Hash, Clone, Copy, Eq, PartialEq, Ord, PartialOrd)]
+pub enum Ambiguity {
+  /// Use the earlier instant of a repeated local time
+  Earliest,
+  /// Use the later instant of a repeated local time
+  Latest,
+  /// Refuse to resolve skipped or repeated local times
+  Reject,
+}
+
+/// Settings which influence how an expression is parsed and resolved
+#[derive(Debug, Clone)]
+pub struct Options {
+  pub language: &'static dyn Language,
+  /// Defaults to `Us`, or `Uk` for languages which write dates day first
+  pub dialect: Option<Dialect>,
+  pub week_start: WeekStart,
+  /// Defaults to `Closest` for US and `Period` for UK English
+  pub direction: Option<DirectionPolicy>,
+  /// Defaults to `Preserve` for relative dates like "1 month"
+  /// and `StartOfDay` for named and absolute dates like "friday"
+  pub time_policy: Option<TimePolicy>,
+  /// Without a bias, "friday" is the next Friday,
+  /// "march" and "5 may" are in the current year
+  /// and log times without a year in the last 12 months
+  pub bias: Option<Bias>,
+  pub ambiguity: Ambiguity,
+  pub times_of_day: TimesOfDay,
+  /// Fail on trailing text instead of ignoring it
+  pub strict: bool,
+  /// Zone of the user, which decides ambiguous abbreviations like `CST`
+  pub zone: Option<chrono_tz::Tz>,
+  /// Zones which decide ambiguous abbreviations otherwise, in order
+  pub preferred_zones: Vec<chrono_tz::Tz>,
+}
+
+impl Default for Options {
+  fn default() -> Options {
+    Options {
+      language: &ENGLISH,
+      dialect: None,
+      week_start: WeekStart::Monday,
+      direction: None,
+      time_policy: None,
+      bias: None,
+      ambiguity: Ambiguity::Earliest,
+      times_of_day: TimesOfDay::default(),
+      strict: false,
+      zone: None,
+      preferred_zones: Vec::new(),
+    }
+  }
+}
+
+impl Options {
+  pub fn dialect(&self) -> Dialect {
+    self.dialect.unwrap_or(if self.language.day_first() {
+      Dialect::Uk
+    }
+    else {
+      Dialect::Us
+    })
+  }
+
+  pub fn american(&self) -> bool {
+    self.dialect() == Dialect::Us
+  }
+
+  pub fn direction_policy(&self) -> DirectionPolicy {
+    self.direction.unwrap_or(match self.dialect {
+      Some(Dialect::Uk) => DirectionPolicy::Period,
+      _ => DirectionPolicy::Closest,
+    })
+  }
+
+  pub fn time_policy(&self, date: &DateSpec) -> TimePolicy {
+    self.time_policy.unwrap_or(match date {
+      DateSpec::Relative(_) => TimePolicy::Preserve,
+      _ => TimePolicy::StartOfDay,
+    })
+  }
+}
+
 pub fn parse_date_string<Tz: TimeZone>(
   s: &str,
   now: DateTime<Tz>,
-  dialect: Dialect,
+  options: &Options,
 ) -> DateResult<DateTime<Tz>>
 where
   Tz::Offset: Copy,
 {
-  let mut dp = parser::DateParser::new(s);
-  if let Dialect::Us = dialect {
+  let spec = parse_spec(s, options)?;
+  resolve(&spec, now, options, &mut Trace::default())
+}
+
+/// Parse an expression without resolving it to a point in time
+pub fn parse_spec(s: &str, options: &Options) -> DateResult<DateTimeSpec> {
+  let prepared = options.language.prepare(s);
+  let mut dp = parser::DateParser::new(&prepared).language(options.language);
+  if options.american() {
     dp = dp.american_date();
   }
-  let d = dp.parse()?;
+  if options.strict {
+    dp = dp.strict();
+  }
+  dp = dp.times_of_day(options.times_of_day.clone());
+  dp.parse()
+}
+
+/// Resolve a parsed expression relative to `now`,
+/// recording each step in `trace`
+pub fn resolve<Tz: TimeZone>(
+  d: &DateTimeSpec,
+  now: DateTime<Tz>,
+  options: &Options,
+  trace: &mut Trace,
+) -> DateResult<DateTime<Tz>>
+where
+  Tz::Offset: Copy,
+{
+  trace.push("reference time", &now);
 
   // we may have explicit hour:minute:sec
-  let tspec = match d.time {
-    Some(tspec) => tspec,
+  let mut tspec = match d.time {
+    Some(ref tspec) => tspec.clone(),
     None => TimeSpec::new_empty(),
   };
-  if tspec.offset.is_some() {
-    //   return DateTime::fix()::parse_from_rfc3339(s);
+  if let Some(abbrev) = tspec.zone_abbrev.take() {
+    // the date decides which offset an abbreviation stands for
+    let local =
+      date_time_of(d, now, tspec.clone(), options, &mut Trace::default())?;
+    let (offset, reason) = resolve_abbreviation(
+      &abbrev,
+      local.naive_local(),
+      options.zone,
+      &options.preferred_zones,
+    )?;
+    trace.push(format!("{abbrev} is {offset}, {reason}"), &local);
+    tspec.offset = Some(offset.local_minus_utc() as i64);
+  }
+  if let Some(zone) = tspec.zone.take() {
+    // 'tomorrow 9am in New York' is tomorrow there, at its offset then
+    let there = now.with_timezone(&zone);
+    trace.push(format!("in {}", zone.name()), &there);
+    let date_time = date_time_of(d, there, tspec, options, trace)?;
+    return Ok(date_time.with_timezone(&now.timezone()));
   }
-  let date_time = if let Some(dspec) = d.date {
-    dspec
-      .to_date_time(now, tspec, dp.american)
-      .or_err("bad date")?
+  date_time_of(d, now, tspec, options, trace)
+}
+
+fn date_time_of<Tz: TimeZone>(
+  d: &DateTimeSpec,
+  now: DateTime<Tz>,
+  tspec: TimeSpec,
+  options: &Options,
+  trace: &mut Trace,
+) -> DateResult<DateTime<Tz>>
+where
+  Tz::Offset: Copy,
+{
+  let date_time = if let Some(ref dspec) = d.date {
+    // days are added as 24 hours, which keeps the time of day
+    // and fractions like 2.5 days
+    let policy = options.time_policy(dspec);
+    let keeps_time = !tspec.empty()
+      || match dspec {
+        DateSpec::Relative(Skip { unit, .. }) => match unit {
+          Interval::Seconds(_) => true,
+          Interval::Days(_) => policy == TimePolicy::Preserve,
+          Interval::Months(_) => false,
+        },
+        // 'start of the hour'
+        DateSpec::Period(PeriodSpec {
+          unit: Interval::Seconds(_),
+          ..
+        }) => true,
+        _ => false,
+      };
+    let date_time = dspec.to_date_time(now, tspec, options, trace)?;
+    if keeps_time {
+      date_time
+    }
+    else {
+      // the same time of day for all kinds of dates
+      let time = match policy {
+        TimePolicy::Preserve => now.time(),
+        TimePolicy::StartOfDay => NaiveTime::MIN,
+      };
+      let naive = date_time.date_naive().and_time(time);
+      let date_time = from_local(&now.timezone(), &naive, options.ambiguity)
+        .or_err("bad time")?;
+      trace.push(format!("time of day by policy {policy:?}"), &date_time);
+      date_time
+    }
   }
   else {
     // no date, time set for today's date
-    tspec.to_date_time(now.date()).or_err("bad time")?
+    tspec
+      .to_date_time(now.date(), options, trace)
+      .or_err("bad time")?
   };
   Ok(date_time)
 }
@@ -67,7 +429,9 @@ pub fn parse_duration(s: &str) -> DateResult<Interval> {
 
   match d.date.unwrap() {
     DateSpec::Absolute(_) => date_result("unexpected absolute date"),
-    DateSpec::FromName(_) => date_result("unexpected date component"),
+    DateSpec::FromName(_) | DateSpec::Week(_) | DateSpec::Period(_) => {
+      date_result("unexpected date component")
+    }
     DateSpec::Relative(skip) => Ok(skip.to_interval()),
   }
 }
@@ -1,5 +1,9 @@
+use super::abbreviations::abbreviations;
+use super::cities::zone_named;
 use super::errors::*;
+use super::language::{Keyword, Language, NumberWord, ENGLISH};
 use super::types::*;
+use chrono::{NaiveTime, Timelike};
 use scanlex::{Scanner, Token};
 
 // when we parse dates, there's often a bit of time parsed..
@@ -17,6 +21,15 @@ pub struct DateParser<'a> {
   direct: Direction,
   maybe_time: Option<(u32, TimeKind)>,
   pub american: bool, // 9/11, not 20/03
+  strict: bool,       // trailing text is an error
+  times: TimesOfDay,  // 'noon', 'evening', …
+  lang: &'static dyn Language,
+  // time implied by the date, like the night in 'tonight'
+  default_time: Option<TimeSpec>,
+  // word after a time which turned out not to be a date, like 'PST'
+  after_time: Option<String>,
+  // 'start of tomorrow' is its midnight
+  start_of: bool,
 }
 
 impl<'a> DateParser<'a> {
@@ -26,6 +39,12 @@ impl<'a> DateParser<'a> {
       direct: Direction::Here,
       maybe_time: None,
       american: false,
+      strict: false,
+      times: TimesOfDay::default(),
+      lang: &ENGLISH,
+      default_time: None,
+      after_time: None,
+      start_of: false,
     }
   }
 
@@ -34,54 +53,368 @@ impl<'a> DateParser<'a> {
     self
   }
 
-  fn date_shortcut_offset(name: &str) -> Option<i32> {
+  pub fn strict(mut self) -> DateParser<'a> {
+    self.strict = true;
+    self
+  }
+
+  pub fn times_of_day(mut self, times: TimesOfDay) -> DateParser<'a> {
+    self.times = times;
+    self
+  }
+
+  pub fn language(mut self, lang: &'static dyn Language) -> DateParser<'a> {
+    self.lang = lang;
+    self
+  }
+
+  // time of day implied by a date shortcut
+  fn date_shortcut_time(name: &str) -> Option<&'static str> {
     match name {
-      "now" => Some(0),
-      "today" | "tdy" => Some(0),
-      "yesterday" | "yday" | "ytd" => Some(-1),
-      "tomorrow" | "tmr" | "tmrw" => Some(1),
+      "tonight" => Some("night"),
       _ => None,
     }
   }
 
-  fn timezone_abbrev_offset(name: &str) -> Option<i64> {
-    use chrono::{DateTime, TimeZone as _};
-    use chrono_tz::{OffsetName, TZ_VARIANTS};
-    use std::collections::HashMap;
-    use std::sync::OnceLock;
-
-    // Built once from chrono-tz's full timezone database by iterating every
-    // IANA zone at a NH-winter and NH-summer reference time (covering both
-    // hemispheres and standard/DST pairs alike).  TZ_VARIANTS is in alphabetical
-    // IANA-name order, so first-found wins for ambiguous abbreviations.
-    static ABBREV_MAP: OnceLock<HashMap<String, i64>> = OnceLock::new();
-
-    let map = ABBREV_MAP.get_or_init(|| {
-      let winter = DateTime::from_timestamp(1_577_836_800, 0)
-        .unwrap()
-        .naive_utc(); // 2020-01-01
-      let summer = DateTime::from_timestamp(1_593_561_600, 0)
-        .unwrap()
-        .naive_utc(); // 2020-07-01
-
-      let mut m: HashMap<String, i64> = HashMap::new();
-      for &tz in TZ_VARIANTS.iter() {
-        for &dt in &[winter, summer] {
-          let offset = tz.offset_from_utc_datetime(&dt);
-          if let Some(abbrev) = offset.abbreviation() {
-            m.entry(abbrev.to_ascii_uppercase()).or_insert_with(|| {
-              chrono::Offset::fix(&offset).local_minus_utc() as i64
-            });
+  fn date_shortcut(&mut self, name: &str) -> Option<DateSpec> {
+    let skip = self.lang.date_shortcut_offset(name)?;
+    self.default_time = Self::date_shortcut_time(name)
+      .and_then(|time| self.times.get(time))
+      .map(|time| TimeSpec::new(time.hour(), time.minute(), time.second(), 0));
+    Some(DateSpec::skip(Interval::Days(1), skip as f64))
+  }
+
+  // a time may be followed by a date, like in '4pm tomorrow'
+  fn date_after_time(&mut self) -> DateResult<Option<DateSpec>> {
+    let next_token = self.scanner.get();
+    let date = self.date_from_token(&next_token)?;
+    if date.is_none() {
+      self.after_time = next_token.as_iden().map(str::to_string);
+    }
+    Ok(date)
+  }
+
+  fn date_from_token(
+    &mut self,
+    next_token: &Token,
+  ) -> DateResult<Option<DateSpec>> {
+    Ok(if let Some(next_name) = next_token.as_iden() {
+      let next_name = next_name.to_lowercase();
+      // Check for date shortcuts like "tomorrow", "today", etc.
+      if let Some(date) = self.date_shortcut(&next_name) {
+        Some(date)
+      }
+      else {
+        ByName::from_name(&next_name, self.direct, self.lang)
+          .map(DateSpec::FromName)
+      }
+    }
+    else {
+      None
+    })
+  }
+
+  // can this word start a spelled-out number?
+  fn starts_number(&self, name: &str) -> bool {
+    !matches!(
+      self.lang.number_word(&name.to_lowercase()),
+      None | Some(NumberWord::And | NumberWord::Of)
+    )
+  }
+
+  // can this token be the hour of a spoken time?
+  fn starts_hour(&self, t: &Token) -> bool {
+    t.is_integer() || t.as_iden().is_some_and(|name| self.starts_number(name))
+  }
+
+  // minutes of an hour marker like 'uhr', 'h' or 'h30'
+  fn hour_marker(&self, name: &str) -> Option<i64> {
+    let digits = name
+      .find(|c: char| c.is_ascii_digit())
+      .unwrap_or(name.len());
+    let (marker, minutes) = name.split_at(digits);
+    if !self.lang.is(&marker.to_lowercase(), Keyword::Hour) {
+      return None;
+    }
+    if minutes.is_empty() {
+      Some(0)
+    }
+    else {
+      minutes.parse().ok()
+    }
+  }
+
+  // the unit of an amount like '3 heures', which is however
+  // a time of day unless it follows 'dans' or 'il y a'
+  fn amount_unit(&self, n: f64, name: &str, amount: bool) -> Option<Interval> {
+    let clock = !amount && n <= 24.0 && self.hour_marker(name).is_some();
+    self.lang.time_unit(name).filter(|_| !clock)
+  }
+
+  // the unit of a period like 'hour' in 'start of the hour'
+  fn period_unit(&self, name: &str) -> Option<Interval> {
+    if self.lang.week_day(name).is_some()
+      || self.lang.month_name(name).is_some()
+    {
+      return None;
+    }
+    let unit = self.lang.time_unit(name)?;
+    PeriodSpec::UNITS
+      .iter()
+      .any(|&(period, _)| period == unit)
+      .then_some(unit)
+  }
+
+  // can this token follow the number of a spoken time?
+  fn continues_time(&self, n: f64, next: &Token) -> bool {
+    let name = next.as_iden().map(str::to_lowercase).unwrap_or_default();
+    self.lang.is(&name, Keyword::Past)
+      || self.lang.is(&name, Keyword::To)
+      || self.lang.is(&name, Keyword::Minus)
+      || name == "am"
+      || name == "pm"
+      || self.hour_marker(&name).is_some()
+      || (n < 1.0 && self.lang.fraction_before_hour() && self.starts_hour(next))
+  }
+
+  // 'noon', 'mittag' or a user-defined time of day
+  fn time_of_day(&self, name: &str) -> Option<NaiveTime> {
+    let name = name.to_lowercase();
+    self
+      .times
+      .get(self.lang.time_of_day(&name).unwrap_or(&name))
+  }
+
+  // Value of a spelled-out number like 'twenty one', 'a couple of'
+  // or 'two and a half', and the token following it
+  fn number_words(&mut self, first: &str) -> DateResult<(f64, Token)> {
+    let (mut total, mut current, mut fraction) = (0.0, 0.0, 0.0);
+    let (mut article, mut and, mut any) = (false, false, false);
+    let mut word = first.to_lowercase();
+    let next = loop {
+      match self.lang.number_word(&word) {
+        // 'halb drei' is a fraction followed by an hour
+        Some(NumberWord::Value(_)) if fraction > 0.0 => {
+          break Token::Iden(word);
+        }
+        Some(NumberWord::Value(value)) => {
+          current += value as f64;
+          any = true;
+        }
+        Some(NumberWord::Scale(scale)) => {
+          current = current.max(1.0) * scale as f64;
+          if scale >= 1000 {
+            total += current;
+            current = 0.0;
           }
+          any = true;
         }
+        Some(NumberWord::Fraction(value)) => {
+          // 'three quarters', but 'two and a half'
+          if current > 0.0 && !and {
+            current *= value;
+          }
+          else {
+            fraction += value;
+          }
+          any = true;
+        }
+        Some(NumberWord::Article) => article = true,
+        Some(NumberWord::And) => and = true,
+        Some(NumberWord::Of) => (),
+        None => break Token::Iden(word),
+      }
+      // 'twenty-one'
+      if self.scanner.peek() == '-' {
+        self.scanner.nextch();
+      }
+      let t = self.scanner.get();
+      match t.as_iden() {
+        Some(name) => word = name.to_lowercase(),
+        None => break t,
+      }
+    };
+    if !any && !article {
+      return date_result("expected a number");
+    }
+    let value = total + current + fraction;
+    Ok((if any { value } else { 1.0 }, next))
+  }
+
+  // the hour of a spoken time like 'nine' or '9', and the token following it
+  fn hour(&mut self, t: Token) -> DateResult<(u32, Token)> {
+    match t {
+      Token::Int(_) => Ok((t.to_int_result::<u32>()?, self.scanner.get())),
+      Token::Iden(ref name) if self.starts_number(name) => {
+        let (hour, after) = self.number_words(name)?;
+        Ok((hour as u32, after))
+      }
+      t => date_result(&format!("expected hour, got {t:?}")),
+    }
+  }
+
+  // minutes following an hour like in '15 Uhr 30', '15h30',
+  // 'trois heures et quart' or 'las tres menos cuarto',
+  // and the token following them
+  fn minutes_after_hour(
+    &mut self,
+    mut next: Token,
+  ) -> DateResult<(i64, Token)> {
+    let marker = next
+      .as_iden()
+      .and_then(|name| self.hour_marker(&name.to_lowercase()));
+    if let Some(minutes) = marker {
+      next = self.scanner.get();
+      if minutes > 0 {
+        return Ok((minutes, next));
+      }
+      // 'quinze heures trente'
+      let words = next.as_iden().map(str::to_lowercase).filter(|name| {
+        matches!(self.lang.number_word(name), Some(NumberWord::Value(_)))
+      });
+      if let Some(name) = words {
+        let (minutes, after) = self.number_words(&name)?;
+        return Ok((minutes as i64, after));
+      }
+      if next.is_integer() {
+        return Ok((next.to_int_result::<i64>()?, self.scanner.get()));
+      }
+    }
+    let name = next.as_iden().map(str::to_lowercase).unwrap_or_default();
+    let sign = if self.lang.number_word(&name) == Some(NumberWord::And) {
+      1
+    }
+    else if self.lang.is(&name, Keyword::Minus) {
+      -1
+    }
+    else {
+      return Ok((0, next));
+    };
+    let (n, after) = match self.scanner.get() {
+      t @ Token::Int(_) => {
+        (t.to_int_result::<u32>()? as f64, self.scanner.get())
       }
-      m
-    });
+      Token::Iden(name) if self.starts_number(&name) => {
+        self.number_words(&name)?
+      }
+      t => return date_result(&format!("expected minutes, got {t:?}")),
+    };
+    let minutes = if n < 1.0 { n * 60.0 } else { n } as i64;
+    Ok((sign * minutes, after))
+  }
+
+  // 'nine pm', 'half past nine', 'quarter to 5', 'twenty past ten',
+  // 'halb drei', 'quinze heures trente'
+  // after the leading number has been read.
+  // Returns hour, minute and the token following the time.
+  fn spoken_time(
+    &mut self,
+    n: f64,
+    next: Token,
+  ) -> DateResult<(u32, u32, Token)> {
+    let relation = next.as_iden().map(str::to_lowercase).unwrap_or_default();
+    let past = self.lang.is(&relation, Keyword::Past);
+    let (hour, minutes, mut after) =
+      if past || self.lang.is(&relation, Keyword::To) {
+        // 'half' and 'quarter' are fractions of an hour
+        let minutes = if n < 1.0 { n * 60.0 } else { n } as i64;
+        if !(0..60).contains(&minutes) {
+          return date_result("expected minutes from 0 to 59");
+        }
+        let t = self.scanner.get();
+        let (hour, after) = self.hour(t)?;
+        if hour > 12 && minutes != 0 {
+          return date_result("expected hour from 1 to 12");
+        }
+        (hour, if past { minutes } else { -minutes }, after)
+      }
+      else if n < 1.0
+        && self.lang.fraction_before_hour()
+        && self.starts_hour(&next)
+      {
+        // 'halb drei' is half of the hour before three
+        let (hour, after) = self.hour(next)?;
+        (hour, (n * 60.0) as i64 - 60, after)
+      }
+      else if (1.0..=24.0).contains(&n) || n == 0.0 {
+        // 'tres y media' is half past three
+        let fraction = (n.fract() * 60.0) as i64;
+        let (minutes, after) = self.minutes_after_hour(next)?;
+        (n as u32, fraction + minutes, after)
+      }
+      else {
+        return date_result("expected time unit after number");
+      };
+    let mut hour = hour as i64;
+    if let Some(am_pm) = after.as_iden().map(str::to_lowercase) {
+      if am_pm == "am" || am_pm == "pm" {
+        hour = hour % 12 + if am_pm == "pm" { 12 } else { 0 };
+        after = self.scanner.get();
+      }
+    }
+    let time = (hour * 60 + minutes).rem_euclid(24 * 60);
+    Ok(((time / 60) as u32, (time % 60) as u32, after))
+  }
+
+  // NUMBER UNIT [ago] (e.g. "2 days", "1.5 hours", "an hour and a half ago")
+  fn relative(
+    &mut self,
+    mut n: f64,
+    unit: Interval,
+    sign: bool,
+  ) -> DateResult<DateSpec> {
+    let mut t = self.scanner.get();
+    let and = t
+      .as_iden()
+      .map(str::to_lowercase)
+      .filter(|name| self.lang.number_word(name) == Some(NumberWord::And));
+    if let Some(and) = and {
+      let (fraction, next) = self.number_words(&and)?;
+      n += fraction;
+      t = next;
+    }
+    if sign {
+      n = -n;
+    }
+    if let Some(name) = t.as_iden() {
+      if !sign && self.lang.is(&name.to_lowercase(), Keyword::Ago) {
+        n = -n;
+      }
+      else {
+        return date_result("only expected 'ago'");
+      }
+    }
+    else if let Some(h) = t.to_integer() {
+      self.maybe_time = Some((h as u32, TimeKind::Unknown));
+    }
+    Ok(DateSpec::skip(unit, n))
+  }
 
-    map.get(&name.to_ascii_uppercase()).copied()
+  // a spelled-out number followed by a time unit ('two weeks')
+  // or forming a time of day ('a quarter past nine')
+  fn spoken_number(
+    &mut self,
+    first: &str,
+    sign: bool,
+    amount: bool,
+  ) -> DateResult<Option<DateSpec>> {
+    let (n, next) = self.number_words(first)?;
+    let name = next.as_iden().map(str::to_lowercase).unwrap_or_default();
+    if let Some(u) = self.amount_unit(n, &name, amount) {
+      return Ok(Some(self.relative(n, u, sign)?));
+    }
+    if !self.continues_time(n, &next) {
+      return date_result("expected time unit after number");
+    }
+    let (hour, min, after) = self.spoken_time(n, next)?;
+    self.maybe_time = Some((hour, TimeKind::PreParsed(min)));
+    self.date_from_token(&after)
   }
 
   fn iso_date(&mut self, year: u32) -> DateResult<DateSpec> {
+    if self.scanner.peek().eq_ignore_ascii_case(&'w') {
+      return self.iso_week(year);
+    }
     let month = self.scanner.get_int::<u32>()?;
     self.scanner.get_ch_matching(&['-'])?;
     let day = self.scanner.get_int::<u32>()?;
@@ -97,6 +430,48 @@ impl<'a> DateParser<'a> {
     Ok(DateSpec::absolute(year, month, day))
   }
 
+  // ISO 8601 week date like 2025-W12 or 2025-W12-3
+  fn iso_week(&mut self, year: u32) -> DateResult<DateSpec> {
+    let name = self.scanner.get_iden()?;
+    let week = name[1..]
+      .parse::<u32>()
+      .ok()
+      .or_err("expected week number after 'W'")?;
+    let day = if self.scanner.peek() == '-' {
+      self.scanner.nextch();
+      self.scanner.get_int::<u32>()?
+    }
+    else {
+      1
+    };
+    Ok(DateSpec::Week(WeekSpec::Iso {
+      year: year as i32,
+      week,
+      day,
+    }))
+  }
+
+  // 'next week', 'week 12' or 'week 12 2025'
+  fn week(&mut self) -> DateResult<DateSpec> {
+    if let Some(weeks) = self.direct.periods() {
+      return Ok(DateSpec::Week(WeekSpec::Relative(weeks)));
+    }
+    let week = self.scanner.get_int::<u32>()?;
+    let mut year = None;
+    self.scanner.skip_whitespace();
+    if self.scanner.peek().is_ascii_digit() {
+      let n = self.scanner.get_int::<u32>()?;
+      if n >= 100 {
+        year = Some(n as i32);
+      }
+      else {
+        // not a year, but the hour of a time like 'week 12 9:00'
+        self.maybe_time = Some((n, TimeKind::Unknown));
+      }
+    }
+    Ok(DateSpec::Week(WeekSpec::Numbered { year, week }))
+  }
+
   fn informal_date(&mut self, day_or_month: u32) -> DateResult<DateSpec> {
     let month_or_day = self.scanner.get_int::<u32>()?;
     let (day, month) = if self.american {
@@ -130,31 +505,94 @@ impl<'a> DateParser<'a> {
   fn parse_date(&mut self) -> DateResult<Option<DateSpec>> {
     let mut t = self.scanner.next().or_err("empty date string")?;
 
-    let sign = t.is_char() && t.as_char().unwrap() == '-';
+    let mut sign = t.is_char() && t.as_char().unwrap() == '-';
     if sign {
       t = self.scanner.next().or_err("nothing after '-'")?;
     }
-    if let Some(name) = t.as_iden() {
-      if let Some(skip) = Self::date_shortcut_offset(name) {
-        return Ok(Some(DateSpec::skip(
-          time_unit("day").unwrap(),
-          skip as f64,
-        )));
+    // 'in 3 days', 'vor 3 Tagen', 'il y a 3 jours', or a time
+    // like 'at 5', 'um 15 Uhr', 'a las 10'
+    let mut amount = sign;
+    let mut at = false;
+    while let Some(name) = t.as_iden().map(str::to_lowercase) {
+      if self.lang.is(&name, Keyword::AgoBefore) {
+        sign = true;
+        amount = true;
+      }
+      else if self.lang.is(&name, Keyword::In) {
+        amount = true;
+      }
+      else if self.lang.is(&name, Keyword::At) {
+        at = true;
+      }
+      else {
+        break;
+      }
+      t = self
+        .scanner
+        .next()
+        .or_err(&format!("nothing after '{name}'"))?;
+    }
+    // 'start of week' is the same as 'this week',
+    // 'start of tomorrow' its midnight
+    let lang = self.lang;
+    let is = |t: &Token, keyword| {
+      t.as_iden()
+        .is_some_and(|name| lang.is(&name.to_lowercase(), keyword))
+    };
+    let start_of = is(&t, Keyword::Start);
+    self.start_of = start_of;
+    if start_of {
+      t = self.scanner.next().or_err("nothing after 'start'")?;
+      if is(&t, Keyword::Of) {
+        t = self.scanner.next().or_err("nothing after 'start of'")?;
+      }
+    }
+    let mut got_direction = false;
+    if let Some(name) = t.as_iden().map(str::to_lowercase) {
+      if let Some(date) = self.date_shortcut(&name) {
+        return Ok(Some(date));
       }
       else
       // maybe next or last?
-      if let Some(d) = Direction::from_name(name) {
+      if let Some(d) = self.lang.direction(&name) {
         self.direct = d;
+        got_direction = true;
       }
     }
-    if self.direct != Direction::Here {
+    if got_direction {
       t = self.scanner.next().or_err("nothing after last/next")?;
     }
+    // 'next month', 'start of the hour'
+    if start_of || got_direction {
+      let unit = t
+        .as_iden()
+        .and_then(|name| self.period_unit(&name.to_lowercase()));
+      if let Some(unit) = unit {
+        let skip = self.direct.periods().unwrap_or(0);
+        return Ok(Some(DateSpec::Period(PeriodSpec { unit, skip })));
+      }
+    }
+    if start_of && !got_direction && is(&t, Keyword::Week) {
+      self.direct = Direction::This;
+    }
     Ok(match t {
       Token::Iden(ref name) => {
         let name = name.to_lowercase();
+        if self.lang.is(&name, Keyword::Week) {
+          return Ok(Some(self.week()?));
+        }
+        if self.starts_number(&name) {
+          return self.spoken_number(&name, sign, amount);
+        }
+        // 'noon', 'this evening', 'midnight friday'
+        if let Some(time) = self.time_of_day(&name) {
+          self.maybe_time =
+            Some((time.hour(), TimeKind::PreParsed(time.minute())));
+          return self.date_after_time();
+        }
         // maybe weekday or month name?
-        if let Some(by_name) = ByName::from_name(&name, self.direct) {
+        if let Some(by_name) = ByName::from_name(&name, self.direct, self.lang)
+        {
           // however, MONTH _might_ be followed by DAY, YEAR
           if let Some(month) = by_name.as_month() {
             let t = self.scanner.get();
@@ -258,7 +696,14 @@ impl<'a> DateParser<'a> {
 
         let t = self.scanner.get();
         if t.finished() {
-          // must be a year...
+          // 'a las 10' is an hour, otherwise it must be a year...
+          if at {
+            if n_int > 24 {
+              return date_result("expected hour from 0 to 24");
+            }
+            self.maybe_time = Some((n_int, TimeKind::PreParsed(0)));
+            return Ok(None);
+          }
           return Ok(Some(DateSpec::absolute(n_int, 1, 1)));
         }
         match t {
@@ -266,61 +711,35 @@ impl<'a> DateParser<'a> {
             let day = n_int;
             let name = name.to_lowercase();
             // Case: NUMBER IDEN (e.g., "14 december", "2 days")
-            if let Some(month) = month_name(&name) {
-              // Parsed DAY MONTH (e.g., "14 december").
-              // Stop parsing the date part here. Let the main loop
-              // handle subsequent tokens (like time "11:20" or year).
+            if let Some(month) = self.lang.month_name(&name) {
+              // Parsed DAY MONTH (e.g., "14 december"),
+              // maybe followed by a year ("3 mars 2027").
+              // Let the main loop handle a following time like "11:20".
+              self.scanner.skip_whitespace();
+              if self.scanner.peek().is_ascii_digit() {
+                let n = self.scanner.get_int::<u32>()?;
+                if n >= 100 {
+                  return Ok(Some(DateSpec::absolute(n, month, day)));
+                }
+                self.maybe_time = Some((n, TimeKind::Unknown));
+              }
               Some(DateSpec::from_day_month(day, month, self.direct))
             }
-            else if let Some(u) = time_unit(&name) {
+            else if let Some(u) = self.amount_unit(n_float, &name, amount) {
               // Parsed NUMBER UNIT (e.g., "2 days", "1.5 hours")
-              let mut n = n_float;
-              if sign {
-                n = -n;
-              }
-              else {
-                let t = self.scanner.get();
-                let got_ago = if let Some(name) = t.as_iden() {
-                  if name == "ago" {
-                    n = -n;
-                    true
-                  }
-                  else {
-                    return date_result("only expected 'ago'");
-                  }
-                }
-                else {
-                  false
-                };
-                if !got_ago {
-                  if let Some(h) = t.to_integer() {
-                    self.maybe_time = Some((h as u32, TimeKind::Unknown));
-                  }
-                }
-              }
-              Some(DateSpec::skip(u, n))
+              Some(self.relative(n_float, u, sign)?)
             }
             else if name == "am" || name == "pm" {
               self.maybe_time = Some((n_int, TimeKind::AmPm(name == "pm")));
               // Continue parsing to look for date information after time
-              let next_token = self.scanner.get();
-              if next_token.finished() {
-                None
-              }
-              else if let Some(next_name) = next_token.as_iden() {
-                let next_name = next_name.to_lowercase();
-                // Check for date shortcuts like "tomorrow", "today", etc.
-                if let Some(skip) = Self::date_shortcut_offset(&next_name) {
-                  Some(DateSpec::skip(time_unit("day").unwrap(), skip as f64))
-                }
-                else {
-                  ByName::from_name(&next_name, self.direct)
-                    .map(DateSpec::FromName)
-                }
-              }
-              else {
-                None
-              }
+              self.date_after_time()?
+            }
+            else if self.continues_time(n_float, &t) {
+              // "10 past 3", "15 Uhr", "15h30"
+              let (hour, min, after) =
+                self.spoken_time(n_float, Token::Iden(name))?;
+              self.maybe_time = Some((hour, TimeKind::PreParsed(min)));
+              self.date_from_token(&after)?
             }
             else {
               return date_result("expected month or time unit");
@@ -339,7 +758,7 @@ impl<'a> DateParser<'a> {
               if let Token::Iden(ref name) = next_token {
                 // This looks like "15. Jun" format
                 let name = name.to_lowercase();
-                if let Some(month) = month_name(&name) {
+                if let Some(month) = self.lang.month_name(&name) {
                   let day = n_int;
                   // Check if there's a year following
                   let next_token = self.scanner.get();
@@ -356,6 +775,19 @@ impl<'a> DateParser<'a> {
                   return date_result("expected month name after day with dot");
                 }
               }
+              else if next_token.is_integer() && self.scanner.peek() == '.' {
+                // A date like "24.12.2025" or "24.12."
+                let day = n_int;
+                let month = next_token.to_int_result::<u32>()?;
+                self.scanner.nextch();
+                if self.scanner.peek().is_ascii_digit() {
+                  let year = self.scanner.get_int::<u32>()?;
+                  Some(DateSpec::absolute(year, month, day))
+                }
+                else {
+                  Some(DateSpec::from_day_month(day, month, self.direct))
+                }
+              }
               else if next_token.is_integer() {
                 // This could be a decimal number like "1.5" or time like "11.20"
                 let decimal_part = next_token.to_int_result::<u32>()? as f64;
@@ -367,13 +799,9 @@ impl<'a> DateParser<'a> {
                 let next_token = self.scanner.get();
                 if let Token::Iden(ref name) = next_token {
                   let name = name.to_lowercase();
-                  if let Some(u) = time_unit(&name) {
+                  if let Some(u) = self.lang.time_unit(&name) {
                     // This is a decimal duration like "1.5 hours"
-                    let mut n = n_float;
-                    if sign {
-                      n = -n;
-                    }
-                    Some(DateSpec::skip(u, n))
+                    Some(self.relative(n_float, u, sign)?)
                   }
                   else {
                     // Not a time unit, treat as informal time like "11.20"
@@ -436,11 +864,7 @@ impl<'a> DateParser<'a> {
     else {
       0
     };
-    if tnext.is_none() {
-      Ok(TimeSpec::new(hour, min, sec, micros))
-    }
-    else {
-      let tok = tnext.as_ref().unwrap();
+    if let Some(tok) = tnext.as_ref() {
       if let Some(ch) = tok.as_char() {
         let expecting_offset = match ch {
           '+' | '-' => true,
@@ -488,18 +912,64 @@ impl<'a> DateParser<'a> {
           let final_hour = DateParser::am_pm(&id_lower, hour)?;
           Ok(TimeSpec::new(final_hour, min, sec, micros))
         }
-        else if let Some(offset) = Self::timezone_abbrev_offset(id) {
-          Ok(TimeSpec::new_with_offset(hour, min, sec, offset, micros))
+        else if self.hour_marker(&id_lower) == Some(0) {
+          // '15:30 Uhr'
+          Ok(TimeSpec::new(hour, min, sec, micros))
+        }
+        else if abbreviations().contains_key(&id.to_ascii_uppercase()) {
+          Ok(TimeSpec::new_with_abbrev(hour, min, sec, id, micros))
         }
         else {
-          // Unknown identifier (e.g. "at") — ignore it
-          Ok(TimeSpec::new(hour, min, sec, micros))
+          // 'America/Sao_Paulo', 'in New York'
+          self.zone_suffix(id, TimeSpec::new(hour, min, sec, micros))
         }
       }
       else {
         Ok(TimeSpec::new(hour, min, sec, micros))
       }
     }
+    else {
+      Ok(TimeSpec::new(hour, min, sec, micros))
+    }
+  }
+
+  // The rest of the input as the zone of `ts`, e.g. 'Europe/Paris',
+  // 'PST' or 'in New York', starting with the word `first` if it was
+  // read already. An unknown place after 'in' is an error, other text
+  // is an error when strict and ignored otherwise.
+  fn zone_suffix(
+    &mut self,
+    first: &str,
+    mut ts: TimeSpec,
+  ) -> DateResult<TimeSpec> {
+    let rest = format!("{first}{}", self.scanner.take_rest());
+    let words: Vec<&str> = rest.split_whitespace().collect();
+    let (place, words) = match words.split_first() {
+      Some((word, words))
+        if !words.is_empty()
+          && self.lang.is(&word.to_lowercase(), Keyword::In) =>
+      {
+        (true, words)
+      }
+      _ => (false, &words[..]),
+    };
+    let name = words.join(" ");
+    if name.is_empty() {
+      return Ok(ts);
+    }
+    if abbreviations().contains_key(&name.to_ascii_uppercase()) {
+      ts.zone_abbrev = Some(name.to_ascii_uppercase());
+    }
+    else if let Some(zone) = zone_named(&name) {
+      ts.zone = Some(zone);
+    }
+    else if place {
+      return date_result(&format!("unknown time zone or city '{name}'"));
+    }
+    else if self.strict {
+      return date_result(&format!("unexpected trailing text {rest:?}"));
+    }
+    Ok(ts)
   }
 
   fn informal_time(&mut self, hour: u32) -> DateResult<TimeSpec> {
@@ -564,15 +1034,33 @@ impl<'a> DateParser<'a> {
       }
 
       // Skip "at" connector word if present
-      if let Some(name) = t.as_iden() {
-        if name == "at" {
-          t = self.scanner.get();
-          if t.finished() {
-            return Ok(None);
-          }
+      let at = t
+        .as_iden()
+        .is_some_and(|name| self.lang.is(&name.to_lowercase(), Keyword::At));
+      if at {
+        t = self.scanner.get();
+        if t.finished() {
+          return Ok(None);
         }
       }
 
+      // 'tomorrow noon', 'friday at midnight'
+      if let Some(time) = t.as_iden().and_then(|name| self.time_of_day(name)) {
+        return Ok(Some(TimeSpec::new(
+          time.hour(),
+          time.minute(),
+          time.second(),
+          0,
+        )));
+      }
+
+      // 'tomorrow at nine', 'friday half past three pm'
+      if let Some(name) = t.as_iden().filter(|name| self.starts_number(name)) {
+        let (n, next) = self.number_words(name)?;
+        let (hour, min, _) = self.spoken_time(n, next)?;
+        return Ok(Some(TimeSpec::new(hour, min, 0, 0)));
+      }
+
       let hour = t.to_int_result::<u32>()?;
       Ok(Some(match self.scanner.get() {
         Token::Char(ch) => match ch {
@@ -580,7 +1068,15 @@ impl<'a> DateParser<'a> {
           '.' => self.informal_time(hour)?,
           ch => return date_result(&format!("unexpected char {ch:?}")),
         },
+        // 'morgen um 15 Uhr', 'demain à 15h30'
+        Token::Iden(name) if self.hour_marker(&name).is_some() => {
+          let (hour, min, _) =
+            self.spoken_time(hour as f64, Token::Iden(name))?;
+          TimeSpec::new(hour, min, 0, 0)
+        }
         Token::Iden(name) => DateParser::hour_time(&name, hour)?,
+        // 'mañana a las 10'
+        t if t.finished() && at => TimeSpec::new(hour, 0, 0, 0),
         t => return date_result(&format!("unexpected token {t:?}")),
       }))
     }
@@ -588,7 +1084,36 @@ impl<'a> DateParser<'a> {
 
   pub fn parse(&mut self) -> DateResult<DateTimeSpec> {
     let date = self.parse_date()?;
-    let time = self.parse_time()?;
+    let mut time = self.parse_time()?.or(self.default_time.take());
+    // 'start of tomorrow' is its midnight, but 'start of 4pm' is no period
+    if self.start_of {
+      let seconds = matches!(
+        date,
+        Some(DateSpec::Relative(Skip {
+          unit: Interval::Seconds(_),
+          ..
+        }))
+      );
+      if date.is_none() || seconds || time.is_some() {
+        return date_result(
+          "expected a day, week, month or year after 'start of'",
+        );
+      }
+      time = Some(TimeSpec::new(0, 0, 0, 0));
+    }
+    // '9am in Tokyo', '3pm PST'
+    if let Some(ts) = time.take_if(|ts| {
+      ts.offset.is_none() && ts.zone_abbrev.is_none() && ts.zone.is_none()
+    }) {
+      let first = self.after_time.take().unwrap_or_default();
+      time = Some(self.zone_suffix(&first, ts)?);
+    }
+    if self.strict {
+      let t = self.scanner.get();
+      if !t.finished() {
+        return date_result(&format!("unexpected trailing text {t:?}"));
+      }
+    }
     Ok(DateTimeSpec { date, time })
   }
 }
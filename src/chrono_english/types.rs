@@ -1,28 +1,76 @@
 #![allow(deprecated)]
+use chrono::offset::LocalResult;
 use chrono::prelude::*;
 use chrono::Duration;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::fmt;
+
+use super::errors::{date_result, DateResult, OrErr};
+use super::language::Language;
+use super::lib::{Ambiguity, Bias, DirectionPolicy, Options};
+
+/// A single step taken while resolving a `DateTimeSpec`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Step {
+  pub action: String,
+  pub value: String,
+}
+
+/// Records the steps taken while resolving a `DateTimeSpec`
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct Trace {
+  pub steps: Vec<Step>,
+}
+
+impl Trace {
+  pub fn push<Tz: TimeZone>(
+    &mut self,
+    action: impl ToString,
+    value: &DateTime<Tz>,
+  ) {
+    self.steps.push(Step {
+      action: action.to_string(),
+      value: value.fixed_offset().to_rfc3339(),
+    });
+  }
+
+  pub fn push_date<Tz: TimeZone>(
+    &mut self,
+    action: impl ToString,
+    date: &Date<Tz>,
+  ) {
+    self.steps.push(Step {
+      action: action.to_string(),
+      value: date.naive_local().to_string(),
+    });
+  }
+}
 
 // implements next/last direction in expressions like 'next friday' and 'last 4 july'
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[non_exhaustive]
 pub enum Direction {
   Next,
   Last,
+  This,
   Here,
 }
 
 impl Direction {
-  pub fn from_name(s: &str) -> Option<Direction> {
-    use Direction::*;
-    match s {
-      "next" => Some(Next),
-      "last" => Some(Last),
-      _ => None,
+  /// Offset in weeks or years when counting in whole periods
+  pub fn periods(self) -> Option<i32> {
+    match self {
+      Direction::Next => Some(1),
+      Direction::Last => Some(-1),
+      Direction::This => Some(0),
+      Direction::Here => None,
     }
   }
 }
 
 // this is a day-month with direction, like 'next 10 Dec'
-#[derive(Debug)]
+#[derive(Debug, Clone, Serialize)]
 pub struct YearDate {
   pub direct: Direction,
   pub month: u32,
@@ -30,7 +78,7 @@ pub struct YearDate {
 }
 
 // for expressions like 'friday' and 'July' modifiable with next/last
-#[derive(Debug)]
+#[derive(Debug, Clone, Serialize)]
 pub struct NamedDate {
   pub direct: Direction,
   pub unit: u32,
@@ -43,7 +91,8 @@ impl NamedDate {
 }
 
 // all expressions modifiable with next/last; 'fri', 'jul', '5 may'.
-#[derive(Debug)]
+#[derive(Debug, Clone, Serialize)]
+#[non_exhaustive]
 pub enum ByName {
   WeekDay(NamedDate),
   MonthName(NamedDate),
@@ -57,6 +106,31 @@ fn add_days<Tz: TimeZone>(
   base.checked_add_signed(Duration::days(days))
 }
 
+// the date `bias` prefers of days or months from the first date
+// up to the second, or of instants if `ts` has a time
+fn prefer<Tz: TimeZone>(
+  bias: Bias,
+  dates: Vec<(Date<Tz>, Date<Tz>)>,
+  base: &DateTime<Tz>,
+  ts: &TimeSpec,
+  options: &Options,
+) -> Option<Date<Tz>> {
+  let mut trace = Trace::default();
+  let mut periods = Vec::new();
+  for (first, end) in &dates {
+    let start = ts.to_date_time(first.clone(), options, &mut trace)?;
+    let end = if ts.empty() {
+      ts.to_date_time(end.clone(), options, &mut trace)?
+    }
+    else {
+      start.clone()
+    };
+    periods.push((start, end));
+  }
+  let i = bias.choose(&periods, base)?;
+  Some(dates[i].0.clone())
+}
+
 //fn next_last_direction<Tz: TimeZone>(date: Date<Tz>, base: Date<Tz>, direct: Direction) -> Option<i32> {
 
 fn next_last_direction<T: PartialOrd>(
@@ -76,12 +150,32 @@ fn next_last_direction<T: PartialOrd>(
   res
 }
 
+// the years to move a month or day of the current year by, like weekdays:
+// 'next' is the following one, 'last' the previous one and 'this'
+// the current one or else the following one
+fn closest_years<T: PartialOrd>(
+  date: &T,
+  current: &T,
+  direct: Direction,
+) -> Option<i32> {
+  match direct {
+    Direction::Next if date <= current => Some(1),
+    Direction::This if date < current => Some(1),
+    Direction::Last if date >= current => Some(-1),
+    _ => None,
+  }
+}
+
 impl ByName {
-  pub fn from_name(s: &str, direct: Direction) -> Option<ByName> {
-    Some(if let Some(wd) = week_day(s) {
+  pub fn from_name(
+    s: &str,
+    direct: Direction,
+    language: &dyn Language,
+  ) -> Option<ByName> {
+    Some(if let Some(wd) = language.week_day(s) {
       ByName::WeekDay(NamedDate::new(direct, wd))
     }
-    else if let Some(mn) = month_name(s) {
+    else if let Some(mn) = language.month_name(s) {
       ByName::MonthName(NamedDate::new(direct, mn))
     }
     else {
@@ -100,44 +194,79 @@ impl ByName {
     ByName::DayMonth(YearDate { direct, day, month })
   }
 
+  pub fn direction(&self) -> Direction {
+    match self {
+      ByName::WeekDay(nd) | ByName::MonthName(nd) => nd.direct,
+      ByName::DayMonth(yd) => yd.direct,
+    }
+  }
+
   pub fn to_date_time<Tz: TimeZone>(
     &self,
     base: DateTime<Tz>,
     ts: TimeSpec,
-    american: bool,
+    options: &Options,
+    trace: &mut Trace,
   ) -> Option<DateTime<Tz>>
   where
     <Tz as TimeZone>::Offset: Copy,
   {
     let this_year = base.year();
+    // With `DirectionPolicy::Period`, an explicit next/this/last
+    // selects the following/current/previous week or year
+    let periods = match options.direction_policy() {
+      DirectionPolicy::Period => self.direction().periods(),
+      DirectionPolicy::Closest => None,
+    };
+    // without next/this/last, the bias picks the occurrence
+    let bias = options.bias.filter(|_| self.direction() == Direction::Here);
+    let local_date = |date: Option<NaiveDate>| {
+      base.timezone().from_local_date(&date?).single()
+    };
     match self {
       ByName::WeekDay(ref nd) => {
-        // a plain 'Friday' means the same as 'next Friday'.
-        // an _explicit_ 'next Friday' has dialect-dependent meaning!
-        // In UK English, it means 'Friday of next week',
-        // but in US English, just the next Friday
-        let mut extra_week = 0;
-        let mut direction = nd.direct;
-        match direction {
-          Direction::Here => direction = Direction::Next,
-          Direction::Next => {
-            if !american {
-              extra_week = 7;
-            }
-          }
-          _ => (),
-        };
-        let this_day = base.weekday().num_days_from_monday() as i64;
-        let that_day = nd.unit as i64;
+        let name = Weekday::try_from(nd.unit as u8).ok()?;
+        let week_start = options.week_start;
+        let this_day = week_start.days_into_week(base.weekday());
+        let that_day = week_start.days_into_week(name);
         let diff_days = that_day - this_day;
         let mut date = add_days(base, diff_days)?;
+        trace.push(format!("{name} of the current week"), &date);
+        if let Some(bias) = bias {
+          let dates = (-1..=1)
+            .map(|weeks| {
+              let day = add_days(date, 7 * weeks)?.date();
+              Some((day, day.succ_opt()?))
+            })
+            .collect::<Option<Vec<_>>>()?;
+          let day = prefer(bias, dates, &base, &ts, options)?;
+          trace.push_date(format!("{name} by bias {bias:?}"), &day);
+          return ts.to_date_time(day, options, trace);
+        }
+        if let Some(weeks) = periods {
+          date = add_days(date, 7 * weeks as i64)?;
+          trace.push(format!("moved {weeks} week(s) ({:?})", nd.direct), &date);
+          return ts.to_date_time(date.date(), options, trace);
+        }
+        // a plain 'Friday' means the same as 'next Friday' or 'this Friday':
+        // the closest Friday from now on, except that 'this Friday'
+        // on a Friday is today
+        let direction = match nd.direct {
+          Direction::Here | Direction::This => Direction::Next,
+          direction => direction,
+        };
         if let Some(correct) = next_last_direction(&date, &base, direction) {
           date = add_days(date, 7 * correct as i64)?;
+          trace.push(format!("moved {correct} week(s) ({direction:?})"), &date);
+        }
+        if diff_days == 0 && nd.direct == Direction::This {
+          trace.push(format!("this {name} is today"), &date);
         }
-        if extra_week > 0 {
-          date = add_days(date, extra_week)?;
+        else if diff_days == 0 && nd.direct == Direction::Last {
+          date = add_days(date, -7)?;
+          trace.push(format!("last {name} is a week ago"), &date);
         }
-        if diff_days == 0 {
+        else if diff_days == 0 {
           // same day - comparing times will determine which way we swing...
           let base_time = base.time();
           let this_time = NaiveTime::from_hms(ts.hour, ts.min, ts.sec);
@@ -145,43 +274,83 @@ impl ByName {
             next_last_direction(&this_time, &base_time, direction)
           {
             date = add_days(date, 7 * correct as i64)?;
+            trace.push(
+              format!("same weekday, moved {correct} week(s) by time"),
+              &date,
+            );
           }
         }
-        ts.to_date_time(date.date())
+        ts.to_date_time(date.date(), options, trace)
       }
       ByName::MonthName(ref nd) => {
+        if let Some(bias) = bias {
+          let dates = (this_year - 1..=this_year + 1)
+            .filter_map(|year| {
+              let first = NaiveDate::from_ymd_opt(year, nd.unit, 1);
+              let end = first?.checked_add_months(chrono::Months::new(1));
+              Some((local_date(first)?, local_date(end)?))
+            })
+            .collect();
+          let date = prefer(bias, dates, &base, &ts, options)?;
+          trace.push_date(format!("month {} by bias {bias:?}", nd.unit), &date);
+          return ts.to_date_time(date, options, trace);
+        }
         let mut date =
           base.timezone().ymd_opt(this_year, nd.unit, 1).single()?;
+        trace
+          .push_date(format!("month {} of the current year", nd.unit), &date);
         if let Some(correct) =
-          next_last_direction(&date, &base.date(), nd.direct)
+          periods.or_else(|| closest_years(&nd.unit, &base.month(), nd.direct))
         {
           date = base
             .timezone()
             .ymd_opt(this_year + correct, nd.unit, 1)
             .single()?;
+          trace.push_date(
+            format!("moved {correct} year(s) ({:?})", nd.direct),
+            &date,
+          );
         }
-        ts.to_date_time(date)
+        ts.to_date_time(date, options, trace)
       }
       ByName::DayMonth(ref yd) => {
+        if let Some(bias) = bias {
+          // Feb 29 only exists in leap years
+          let dates = (this_year - 4..=this_year + 4)
+            .filter_map(|year| {
+              let day = NaiveDate::from_ymd_opt(year, yd.month, yd.day);
+              Some((local_date(day)?, local_date(day?.succ_opt())?))
+            })
+            .collect();
+          let date = prefer(bias, dates, &base, &ts, options)?;
+          trace.push_date(format!("day and month by bias {bias:?}"), &date);
+          return ts.to_date_time(date, options, trace);
+        }
         let mut date = base
           .timezone()
           .ymd_opt(this_year, yd.month, yd.day)
           .single()?;
-        if let Some(correct) =
-          next_last_direction(&date, &base.date(), yd.direct)
+        trace.push_date("day and month in the current year", &date);
+        let current = (base.month(), base.day());
+        if let Some(correct) = periods
+          .or_else(|| closest_years(&(yd.month, yd.day), &current, yd.direct))
         {
           date = base
             .timezone()
             .ymd_opt(this_year + correct, yd.month, yd.day)
             .single()?;
+          trace.push_date(
+            format!("moved {correct} year(s) ({:?})", yd.direct),
+            &date,
+          );
         }
-        ts.to_date_time(date)
+        ts.to_date_time(date, options, trace)
       }
     }
   }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Serialize)]
 pub struct AbsDate {
   pub year: i32,
   pub month: u32,
@@ -208,14 +377,17 @@ impl AbsDate {
 // to months, where we want to preserve dates. So adding a month to
 // '5 May' gives '5 June'. Adding a month to '30 Jan' gives 'Feb 28' or 'Feb 29'
 // depending on whether this is a leap year.
-#[derive(Debug, Hash, Clone, Copy, Eq, PartialEq, Ord, PartialOrd)]
+#[derive(
+  Debug, Hash, Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Serialize,
+)]
+#[non_exhaustive]
 pub enum Interval {
   Seconds(i32),
   Days(i32),
   Months(i32),
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Serialize)]
 pub struct Skip {
   pub unit: Interval,
   pub skip: f64,
@@ -226,24 +398,26 @@ impl Skip {
     &self,
     base: DateTime<Tz>,
     ts: TimeSpec,
-  ) -> Option<DateTime<Tz>> {
-    Some(match self.unit {
+    options: &Options,
+    trace: &mut Trace,
+  ) -> DateResult<DateTime<Tz>> {
+    let add_seconds = |secs: i64| {
+      Duration::try_seconds(((secs as f64) * self.skip) as i64)
+        .and_then(|duration| base.clone().checked_add_signed(duration))
+        .or_err("date out of range")
+    };
+    Ok(match self.unit {
       Interval::Seconds(secs) => {
-        base
-          .checked_add_signed(Duration::seconds(
-            ((secs as f64) * self.skip) as i64,
-          ))
-          .unwrap() // <--- !!!!
+        let date = add_seconds(secs.into())?;
+        trace.push(format!("added {} × {secs} seconds", self.skip), &date);
+        date
       }
       Interval::Days(days) => {
-        let secs = 60 * 60 * 24 * days;
-        let date = base
-          .checked_add_signed(Duration::seconds(
-            ((secs as f64) * self.skip) as i64,
-          ))
-          .unwrap();
+        let date = add_seconds(60 * 60 * 24 * i64::from(days))?;
+        trace.push(format!("added {} × {days} days", self.skip), &date);
         if !ts.empty() {
-          ts.to_date_time(date.date())?
+          ts.to_date_time(date.date(), options, trace)
+            .or_err("bad date")?
         }
         else {
           date
@@ -254,7 +428,7 @@ impl Skip {
           (base.year(), (base.month() - 1) as i32, base.day());
         let delta = ((mm as f64) * self.skip) as i32;
         // Our new month number
-        let month = month + delta;
+        let month = month.checked_add(delta).or_err("date out of range")?;
         // Which may run over to the next year and so forth
         let (year, month) = if month >= 0 {
           (year + month / 12, month % 12 + 1)
@@ -270,14 +444,15 @@ impl Skip {
         // dud dates like Feb 30 may result, so we back off...
         while date.is_none() {
           day -= 1;
-          if day == 0 || day < 28 {
-            // sanity check...
-            eprintln!("fkd date");
-            return None;
+          if day < 28 {
+            // the year is out of range
+            return date_result("date out of range");
           }
           date = base.timezone().ymd_opt(year, month as u32, day).single();
         }
-        ts.to_date_time(date.unwrap())?
+        let date = date.unwrap();
+        trace.push_date(format!("added {delta} months"), &date);
+        ts.to_date_time(date, options, trace).or_err("bad date")?
       }
     })
   }
@@ -293,11 +468,126 @@ impl Skip {
   }
 }
 
-#[derive(Debug)]
+// a whole week, resolved to its first day
+#[derive(Debug, Clone, Serialize)]
+#[non_exhaustive]
+pub enum WeekSpec {
+  // 'this week', 'next week', 'last week'
+  Relative(i32),
+  // 'week 12', 'week 12 2025', numbered according to the week start
+  Numbered { year: Option<i32>, week: u32 },
+  // ISO 8601 week date, e.g. '2025-W12' or '2025-W12-3'
+  Iso { year: i32, week: u32, day: u32 },
+}
+
+impl WeekSpec {
+  pub fn to_date<Tz: TimeZone>(
+    &self,
+    base: &DateTime<Tz>,
+    options: &Options,
+  ) -> Option<Date<Tz>> {
+    let week_start = options.week_start;
+    let date = match *self {
+      WeekSpec::Relative(weeks) => {
+        let today = base.date_naive();
+        today - Duration::days(week_start.days_into_week(today.weekday()))
+          + Duration::weeks(weeks as i64)
+      }
+      WeekSpec::Numbered { year, week } => {
+        let year =
+          year.unwrap_or_else(|| week_start.week_number(base.date_naive()).0);
+        week_start.week_start_date(year, week)?
+      }
+      WeekSpec::Iso { year, week, day } => NaiveDate::from_isoywd_opt(
+        year,
+        week,
+        Weekday::try_from(day.checked_sub(1)? as u8).ok()?,
+      )?,
+    };
+    base.timezone().from_local_date(&date).single()
+  }
+}
+
+// the start of the second, minute, hour, day, month or year `skip` units
+// from the one of the reference time, e.g. 'start of the hour'
+#[derive(Debug, Clone, Serialize)]
+pub struct PeriodSpec {
+  pub unit: Interval,
+  pub skip: i32,
+}
+
+impl PeriodSpec {
+  // the units a period can be, with their names
+  pub const UNITS: [(Interval, &'static str); 6] = [
+    (Interval::Seconds(1), "second"),
+    (Interval::Seconds(60), "minute"),
+    (Interval::Seconds(60 * 60), "hour"),
+    (Interval::Days(1), "day"),
+    (Interval::Months(1), "month"),
+    (Interval::Months(12), "year"),
+  ];
+
+  pub fn name(&self) -> &'static str {
+    Self::UNITS
+      .iter()
+      .find(|(unit, _)| *unit == self.unit)
+      .map_or("period", |&(_, name)| name)
+  }
+
+  pub fn to_date_time<Tz: TimeZone>(
+    &self,
+    base: &DateTime<Tz>,
+    ts: TimeSpec,
+    options: &Options,
+    trace: &mut Trace,
+  ) -> Option<DateTime<Tz>> {
+    let today = base.date_naive();
+    let date = match self.unit {
+      Interval::Seconds(secs) => {
+        let secs = secs as i64;
+        let elapsed = base.time().num_seconds_from_midnight() as i64;
+        let start = today.and_time(NaiveTime::MIN)
+          + Duration::seconds(
+            elapsed - elapsed % secs + secs * self.skip as i64,
+          );
+        let date_time =
+          from_local(&base.timezone(), &start, options.ambiguity)?;
+        trace.push(format!("start of the {}", self.name()), &date_time);
+        return Some(date_time);
+      }
+      Interval::Days(days) => today + Duration::days((days * self.skip) as i64),
+      Interval::Months(months) => {
+        let first = today.with_day(1)?;
+        let first = if months == 12 {
+          first.with_month(1)?
+        }
+        else {
+          first
+        };
+        let months = months * self.skip;
+        if months < 0 {
+          first
+            .checked_sub_months(chrono::Months::new(months.unsigned_abs()))?
+        }
+        else {
+          first.checked_add_months(chrono::Months::new(months as u32))?
+        }
+      }
+    };
+    let date = base.timezone().from_local_date(&date).single()?;
+    trace.push_date(format!("start of the {}", self.name()), &date);
+    ts.to_date_time(date, options, trace)
+  }
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[non_exhaustive]
 pub enum DateSpec {
-  Absolute(AbsDate), // Y M D (e.g. 2018-06-02, 4 July 2017)
-  Relative(Skip),    // n U (e.g. 2min, 3 years ago, -2d)
-  FromName(ByName),  // (e.g. 'next fri', 'jul')
+  Absolute(AbsDate),  // Y M D (e.g. 2018-06-02, 4 July 2017)
+  Relative(Skip),     // n U (e.g. 2min, 3 years ago, -2d)
+  FromName(ByName),   // (e.g. 'next fri', 'jul')
+  Week(WeekSpec),     // (e.g. 'next week', 'week 12', '2025-W12')
+  Period(PeriodSpec), // (e.g. 'start of the hour', 'next month')
 }
 
 impl DateSpec {
@@ -321,27 +611,50 @@ impl DateSpec {
     &self,
     base: DateTime<Tz>,
     ts: TimeSpec,
-    american: bool,
-  ) -> Option<DateTime<Tz>>
+    options: &Options,
+    trace: &mut Trace,
+  ) -> DateResult<DateTime<Tz>>
   where
     Tz::Offset: Copy,
   {
     use DateSpec::*;
     match self {
-      Absolute(ref ad) => ts.to_date_time(ad.to_date(base)?),
-      Relative(ref skip) => skip.to_date_time(base, ts), // might need time
-      FromName(ref byname) => byname.to_date_time(base, ts, american),
+      Absolute(ref ad) => {
+        let date = ad.to_date(base).or_err("bad date")?;
+        trace.push_date("absolute date", &date);
+        ts.to_date_time(date, options, trace).or_err("bad date")
+      }
+      Relative(ref skip) => skip.to_date_time(base, ts, options, trace), // might need time
+      FromName(ref byname) => byname
+        .to_date_time(base, ts, options, trace)
+        .or_err("bad date"),
+      Week(ref week) => {
+        let date = week.to_date(&base, options).or_err("bad date")?;
+        trace.push_date(
+          format!("start of week, weeks starting {:?}", options.week_start),
+          &date,
+        );
+        ts.to_date_time(date, options, trace).or_err("bad date")
+      }
+      Period(ref period) => period
+        .to_date_time(&base, ts, options, trace)
+        .or_err("bad date"),
     }
   }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Serialize)]
 pub struct TimeSpec {
   pub hour: u32,
   pub min: u32,
   pub sec: u32,
   pub empty: bool,
   pub offset: Option<i64>,
+  /// A zone abbreviation like `CST`, whose offset depends on the date
+  /// and the zone of the user
+  pub zone_abbrev: Option<String>,
+  /// A zone like `Europe/Paris`, in which the whole expression is read
+  pub zone: Option<chrono_tz::Tz>,
   pub microsec: u32,
 }
 
@@ -353,6 +666,8 @@ impl TimeSpec {
       sec,
       empty: false,
       offset: None,
+      zone_abbrev: None,
+      zone: None,
       microsec,
     }
   }
@@ -370,10 +685,25 @@ impl TimeSpec {
       sec,
       empty: false,
       offset: Some(offset),
+      zone_abbrev: None,
+      zone: None,
       microsec,
     }
   }
 
+  pub fn new_with_abbrev(
+    hour: u32,
+    min: u32,
+    sec: u32,
+    abbrev: &str,
+    microsec: u32,
+  ) -> TimeSpec {
+    TimeSpec {
+      zone_abbrev: Some(abbrev.to_ascii_uppercase()),
+      ..TimeSpec::new(hour, min, sec, microsec)
+    }
+  }
+
   pub fn new_empty() -> TimeSpec {
     TimeSpec {
       hour: 0,
@@ -381,6 +711,8 @@ impl TimeSpec {
       sec: 0,
       empty: true,
       offset: None,
+      zone_abbrev: None,
+      zone: None,
       microsec: 0,
     }
   }
@@ -392,89 +724,256 @@ impl TimeSpec {
   pub fn to_date_time<Tz: TimeZone>(
     &self,
     date: Date<Tz>,
+    options: &Options,
+    trace: &mut Trace,
   ) -> Option<DateTime<Tz>> {
-    let dt = date.and_hms_micro(self.hour, self.min, self.sec, self.microsec);
-    if let Some(offs) = self.offset {
-      let zoffset = dt.offset().clone();
-      let tstamp =
-        dt.timestamp() - offs + zoffset.fix().local_minus_utc() as i64;
-      let nd = NaiveDateTime::from_timestamp(tstamp, 1000 * self.microsec);
-      Some(DateTime::from_utc(nd, zoffset))
+    let naive = date.naive_local().and_hms_micro_opt(
+      self.hour,
+      self.min,
+      self.sec,
+      self.microsec,
+    )?;
+    let date_time = if let Some(offs) = self.offset {
+      let utc = naive.checked_sub_signed(Duration::seconds(offs))?;
+      date.timezone().from_utc_datetime(&utc)
     }
     else {
-      Some(dt)
+      from_local(&date.timezone(), &naive, options.ambiguity)?
+    };
+    let action = match self.offset {
+      Some(offs) => format!("set time {} at offset {offs}s", naive.time()),
+      None => format!("set time {}", naive.time()),
+    };
+    trace.push(action, &date_time);
+    Some(date_time)
+  }
+}
+
+/// Resolve a local date and time in the given zone.
+/// A time skipped by a daylight saving transition is moved forward
+/// by the length of the gap, like a wall clock would be.
+pub fn from_local<Tz: TimeZone>(
+  tz: &Tz,
+  naive: &NaiveDateTime,
+  ambiguity: Ambiguity,
+) -> Option<DateTime<Tz>> {
+  match (tz.from_local_datetime(naive), ambiguity) {
+    (LocalResult::Single(dt), _) => Some(dt),
+    (LocalResult::Ambiguous(early, _), Ambiguity::Earliest) => Some(early),
+    (LocalResult::Ambiguous(_, late), Ambiguity::Latest) => Some(late),
+    (LocalResult::None, Ambiguity::Earliest | Ambiguity::Latest) => {
+      let before = naive.checked_sub_signed(Duration::days(1))?;
+      let offset = tz.offset_from_local_datetime(&before).earliest()?;
+      let utc = naive.checked_sub_signed(Duration::seconds(
+        offset.fix().local_minus_utc() as i64,
+      ))?;
+      Some(tz.from_utc_datetime(&utc))
     }
+    _ => None,
   }
 }
 
-#[derive(Debug)]
+/// Named times of day like 'noon' or 'evening'
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TimesOfDay {
+  times: BTreeMap<String, NaiveTime>,
+}
+
+impl Default for TimesOfDay {
+  fn default() -> TimesOfDay {
+    let mut times = TimesOfDay {
+      times: BTreeMap::new(),
+    };
+    for (names, hour) in [
+      (&["midnight"][..], 0),
+      (&["morning"], 9),
+      (&["noon", "midday"], 12),
+      (&["afternoon"], 15),
+      (&["eod", "eob", "cob"], 17), // end of day/business, close of business
+      (&["evening"], 18),
+      (&["night"], 21),
+    ] {
+      for name in names {
+        times.set(name, NaiveTime::from_hms_opt(hour, 0, 0).unwrap());
+      }
+    }
+    times
+  }
+}
+
+impl TimesOfDay {
+  pub fn get(&self, name: &str) -> Option<NaiveTime> {
+    self.times.get(&name.to_lowercase()).copied()
+  }
+
+  /// Add a name or change the time of an existing one
+  pub fn set(&mut self, name: &str, time: NaiveTime) {
+    self.times.insert(name.to_lowercase(), time);
+  }
+}
+
+#[derive(Debug, Clone, Serialize)]
 pub struct DateTimeSpec {
   pub date: Option<DateSpec>,
   pub time: Option<TimeSpec>,
 }
 
-// same as chrono's 'count days from monday' convention
-pub fn week_day(s: &str) -> Option<u32> {
-  if s.len() < 3 {
-    return None;
-  }
-  Some(match &s[0..3] {
-    "sun" => 6,
-    "mon" => 0,
-    "tue" => 1,
-    "wed" => 2,
-    "thu" => 3,
-    "fri" => 4,
-    "sat" => 5,
-    _ => return None,
-  })
-}
-
-pub fn month_name(s: &str) -> Option<u32> {
-  if s.len() < 3 {
-    return None;
-  }
-  Some(match &s[0..3] {
-    "jan" => 1,
-    "feb" => 2,
-    "mar" => 3,
-    "apr" => 4,
-    "may" => 5,
-    "jun" => 6,
-    "jul" => 7,
-    "aug" => 8,
-    "sep" => 9,
-    "oct" => 10,
-    "nov" => 11,
-    "dec" => 12,
-    _ => return None,
-  })
-}
-
-pub fn time_unit(s: &str) -> Option<Interval> {
-  use Interval::*;
-  let name = if s.len() < 3 {
-    match &s[0..1] {
-      "s" => "sec",
-      "m" => "min",
-      "h" => "hou",
-      "w" => "wee",
-      "d" => "day",
-      "y" => "yea",
-      _ => return None,
+// readable forms for explanations, e.g. 'weekday: Friday, direction: next'
+
+const WEEKDAY_NAMES: [&str; 7] = [
+  "Monday",
+  "Tuesday",
+  "Wednesday",
+  "Thursday",
+  "Friday",
+  "Saturday",
+  "Sunday",
+];
+
+const MONTH_NAMES: [&str; 12] = [
+  "January",
+  "February",
+  "March",
+  "April",
+  "May",
+  "June",
+  "July",
+  "August",
+  "September",
+  "October",
+  "November",
+  "December",
+];
+
+fn month_name(month: u32) -> String {
+  month
+    .checked_sub(1)
+    .and_then(|i| MONTH_NAMES.get(i as usize))
+    .map_or_else(|| month.to_string(), |name| name.to_string())
+}
+
+impl fmt::Display for Direction {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match self {
+      Direction::Next => write!(f, ", direction: next"),
+      Direction::Last => write!(f, ", direction: last"),
+      Direction::This => write!(f, ", direction: this"),
+      Direction::Here => Ok(()),
+    }
+  }
+}
+
+impl fmt::Display for ByName {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match self {
+      ByName::WeekDay(nd) => {
+        let name = WEEKDAY_NAMES.get(nd.unit as usize).unwrap_or(&"?");
+        write!(f, "weekday: {name}{}", nd.direct)
+      }
+      ByName::MonthName(nd) => {
+        write!(f, "month: {}{}", month_name(nd.unit), nd.direct)
+      }
+      ByName::DayMonth(yd) => {
+        write!(
+          f,
+          "day: {}, month: {}{}",
+          yd.day,
+          month_name(yd.month),
+          yd.direct
+        )
+      }
+    }
+  }
+}
+
+impl fmt::Display for Interval {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    let (count, unit) = match *self {
+      Interval::Seconds(secs) => (secs, "second"),
+      Interval::Days(days) => (days, "day"),
+      Interval::Months(months) => (months, "month"),
+    };
+    let plural = if count == 1 { "" } else { "s" };
+    write!(f, "{count} {unit}{plural}")
+  }
+}
+
+impl fmt::Display for PeriodSpec {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    let name = self.name();
+    match self.skip {
+      0 => write!(f, "{name}: this"),
+      1 => write!(f, "{name}: next"),
+      -1 => write!(f, "{name}: last"),
+      skip => write!(f, "{name}: {skip:+} from this one"),
+    }
+  }
+}
+
+impl fmt::Display for WeekSpec {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match *self {
+      WeekSpec::Relative(0) => write!(f, "week: this"),
+      WeekSpec::Relative(1) => write!(f, "week: next"),
+      WeekSpec::Relative(-1) => write!(f, "week: last"),
+      WeekSpec::Relative(weeks) => write!(f, "week: {weeks:+} from this one"),
+      WeekSpec::Numbered { year: None, week } => write!(f, "week: {week}"),
+      WeekSpec::Numbered {
+        year: Some(year),
+        week,
+      } => write!(f, "week: {week}, year: {year}"),
+      WeekSpec::Iso { year, week, day } => {
+        write!(f, "ISO week: {week}, year: {year}, day: {day}")
+      }
+    }
+  }
+}
+
+impl fmt::Display for DateSpec {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match self {
+      DateSpec::Absolute(ad) => {
+        write!(f, "date: {:04}-{:02}-{:02}", ad.year, ad.month, ad.day)
+      }
+      DateSpec::Relative(skip) => {
+        write!(f, "amount: {}, unit: {}", skip.skip, skip.unit)
+      }
+      DateSpec::FromName(by_name) => write!(f, "{by_name}"),
+      DateSpec::Week(week) => write!(f, "{week}"),
+      DateSpec::Period(period) => write!(f, "{period}"),
+    }
+  }
+}
+
+impl fmt::Display for TimeSpec {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    let mut fields = Vec::new();
+    if !self.empty {
+      let mut time =
+        format!("{:02}:{:02}:{:02}", self.hour, self.min, self.sec);
+      if self.microsec > 0 {
+        time += &format!(".{:06}", self.microsec);
+      }
+      fields.push(format!("time: {time}"));
+    }
+    if let Some(offset) = self.offset {
+      let sign = if offset < 0 { '-' } else { '+' };
+      let offset = offset.abs();
+      fields.push(format!(
+        "offset: {sign}{:02}:{:02}",
+        offset / 3600,
+        offset % 3600 / 60
+      ));
+    }
+    if let Some(abbrev) = &self.zone_abbrev {
+      fields.push(format!("abbreviation: {abbrev}"));
+    }
+    if let Some(zone) = self.zone {
+      fields.push(format!("zone: {}", zone.name()));
+    }
+    if fields.is_empty() {
+      fields.push("none".to_string());
     }
+    write!(f, "{}", fields.join(", "))
   }
-  else {
-    &s[0..3]
-  };
-  Some(match name {
-    "sec" => Seconds(1),
-    "min" => Seconds(60),
-    "hou" => Seconds(60 * 60),
-    "day" => Days(1),
-    "wee" => Days(7),
-    "mon" => Months(1),
-    "yea" => Months(12),
-    _ => return None,
-  })
 }
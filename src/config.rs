@@ -0,0 +1,133 @@
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use chrono_tz::Tz;
+use serde::Deserialize;
+
+use crate::chrono_english::lib::{date_error, DateResult};
+use crate::format::Format;
+use crate::parser::Parser;
+
+/// User settings, usually read from `~/.config/tu/config.toml`:
+///
+/// ```toml
+/// zone = "Europe/Berlin"
+/// dialect = "uk"
+/// format = "local"
+/// time_policy = "start-of-day"
+/// prefer = "past"
+/// prefer_zones = ["Asia/Kolkata"]
+/// show_zones = ["America/Los_Angeles", "Europe/Berlin", "Asia/Tokyo"]
+///
+/// [aliases]
+/// eod = "today 17:00"
+/// "sprint end" = "next friday 18:00"
+///
+/// [dates]
+/// "release day" = "2025-03-14 10:00"
+/// ```
+///
+/// Aliases expand to an expression which is resolved whenever it is used,
+/// dates are resolved once when the config is applied.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+  /// IANA name of the default zone
+  pub zone: Option<String>,
+  /// `us` or `uk`
+  pub dialect: Option<String>,
+  /// Output format of the command line tool
+  pub format: Option<String>,
+  /// `preserve` or `start-of-day`
+  pub time_policy: Option<String>,
+  /// `past`, `future` or `nearest`
+  pub prefer: Option<String>,
+  /// IANA names of zones deciding ambiguous abbreviations
+  pub prefer_zones: Vec<String>,
+  /// IANA names of zones to show the result in, like a world clock
+  pub show_zones: Vec<String>,
+  pub aliases: BTreeMap<String, String>,
+  pub dates: BTreeMap<String, String>,
+}
+
+impl Config {
+  /// `$XDG_CONFIG_HOME/tu/config.toml` or `~/.config/tu/config.toml`
+  pub fn default_path() -> Option<PathBuf> {
+    let config_dir = std::env::var_os("XDG_CONFIG_HOME")
+      .filter(|dir| !dir.is_empty())
+      .map(PathBuf::from)
+      .or_else(|| {
+        std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".config"))
+      })?;
+    Some(config_dir.join("tu").join("config.toml"))
+  }
+
+  /// Read a config file
+  pub fn load(path: &Path) -> DateResult<Config> {
+    let text = std::fs::read_to_string(path).map_err(|e| {
+      date_error(format!("could not read {}: {e}", path.display()))
+    })?;
+    Config::parse(&text)
+      .map_err(|e| date_error(format!("{}: {e}", path.display())))
+  }
+
+  /// Parse the TOML content of a config file
+  pub fn parse(text: &str) -> DateResult<Config> {
+    toml::from_str(text).map_err(|e| date_error(e.message()))
+  }
+
+  /// The configured output format
+  pub fn format(&self) -> DateResult<Option<Format>> {
+    self.format.as_deref().map(str::parse).transpose()
+  }
+
+  /// The configured world clock zones
+  pub fn show_zones(&self) -> DateResult<Vec<Tz>> {
+    self
+      .show_zones
+      .iter()
+      .map(|zone| parse_zone(zone))
+      .collect()
+  }
+
+  /// Apply zone, dialect, time policy, bias, aliases and dates to `parser`
+  pub fn apply(&self, mut parser: Parser) -> DateResult<Parser> {
+    if let Some(zone) = &self.zone {
+      parser = parser.zone(parse_zone(zone)?);
+    }
+    for zone in &self.prefer_zones {
+      parser = parser.prefer_zone(parse_zone(zone)?);
+    }
+    if let Some(dialect) = &self.dialect {
+      parser = parser.dialect(dialect.parse()?);
+    }
+    if let Some(policy) = &self.time_policy {
+      parser = parser.time_policy(policy.parse()?);
+    }
+    if let Some(bias) = &self.prefer {
+      parser = parser.bias(bias.parse()?);
+    }
+    for (name, expression) in &self.aliases {
+      parser = parser.alias(name, expression);
+    }
+    self.apply_dates(parser)
+  }
+
+  /// Resolve the dates relative to the reference time of `parser`,
+  /// e.g. again after it changed
+  pub fn apply_dates(&self, mut parser: Parser) -> DateResult<Parser> {
+    for (name, expression) in &self.dates {
+      let date = parser
+        .parse(expression)
+        .map_err(|e| date_error(format!("date '{name}': {e}")))?;
+      parser = parser.named_date(name, date.utc);
+    }
+    Ok(parser)
+  }
+}
+
+fn parse_zone(name: &str) -> DateResult<Tz> {
+  name
+    .parse()
+    .map_err(|_| date_error(format!("unknown zone '{name}'")))
+}
@@ -0,0 +1,87 @@
+use chrono::Duration;
+
+use crate::chrono_english::lib::DateError;
+use crate::parser::Parser;
+
+impl Parser {
+  /// The time from the reference time to the instant of `input`,
+  /// e.g. 14 days for `2 weeks` or -1 day for `yesterday`
+  pub fn duration(&self, input: &str) -> Result<Duration, DateError> {
+    let now = self.reference_time();
+    let date = self.clone().now(now).parse(input)?;
+    Ok(date.utc - now)
+  }
+
+  /// The time from the instant of `from` to the instant of `to`,
+  /// both relative to the same reference time
+  pub fn diff(&self, from: &str, to: &str) -> Result<Duration, DateError> {
+    let parser = self.clone().now(self.reference_time());
+    Ok(parser.parse(to)?.utc - parser.parse(from)?.utc)
+  }
+}
+
+/// Format a duration like `P1DT2H30M` or `-PT0.5S`.
+/// Days are the largest unit, as months and years vary in length.
+pub fn iso_duration(duration: Duration) -> String {
+  let sign = if duration < Duration::zero() { "-" } else { "" };
+  let duration = duration.abs();
+  let days = duration.num_days();
+  let hours = duration.num_hours() % 24;
+  let minutes = duration.num_minutes() % 60;
+  let nanos = (duration - Duration::minutes(duration.num_minutes()))
+    .num_nanoseconds()
+    .unwrap_or_default();
+
+  let mut time = String::new();
+  if hours > 0 {
+    time += &format!("{hours}H");
+  }
+  if minutes > 0 {
+    time += &format!("{minutes}M");
+  }
+  if nanos > 0 {
+    let seconds =
+      format!("{}.{:09}", nanos / 1_000_000_000, nanos % 1_000_000_000);
+    time += seconds.trim_end_matches('0').trim_end_matches('.');
+    time += "S";
+  }
+  match (days, time.is_empty()) {
+    (0, true) => "PT0S".to_string(),
+    (_, true) => format!("{sign}P{days}D"),
+    (0, false) => format!("{sign}PT{time}"),
+    _ => format!("{sign}P{days}DT{time}"),
+  }
+}
+
+/// Describe a duration from now in words, rounded to its largest unit,
+/// like `in 3 days` or `2 hours ago`, which parses back to about the same time
+pub fn humanize(duration: Duration) -> String {
+  const UNITS: [(&str, i64); 7] = [
+    ("year", 365 * 86_400),
+    ("month", 30 * 86_400),
+    ("week", 7 * 86_400),
+    ("day", 86_400),
+    ("hour", 3_600),
+    ("minute", 60),
+    ("second", 1),
+  ];
+  let seconds = duration.num_seconds();
+  let Some((unit, length)) =
+    UNITS.iter().find(|(_, length)| seconds.abs() >= *length)
+  else {
+    return "now".to_string();
+  };
+  let count = (seconds.abs() as f64 / *length as f64).round() as i64;
+  let amount = if count == 1 {
+    format!("1 {unit}")
+  }
+  else {
+    format!("{count} {unit}s")
+  };
+  if seconds < 0 {
+    format!("{amount} ago")
+  }
+  else {
+    format!("in {amount}")
+  }
+}
@@ -0,0 +1,324 @@
+use chrono::prelude::{DateTime, Utc};
+
+use crate::chrono_english::lib::{date_result, DateError, DateResult};
+use crate::chrono_english::types::Trace;
+
+/// Unit of a timestamp counted from 1970-01-01T00:00:00Z
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum EpochUnit {
+  Seconds,
+  Milliseconds,
+  Microseconds,
+  Nanoseconds,
+}
+
+const NANOS_PER_SECOND: i128 = 1_000_000_000;
+const NANOS_PER_DAY: i128 = 86_400 * NANOS_PER_SECOND;
+
+impl EpochUnit {
+  fn nanos(self) -> i128 {
+    match self {
+      EpochUnit::Seconds => NANOS_PER_SECOND,
+      EpochUnit::Milliseconds => 1_000_000,
+      EpochUnit::Microseconds => 1_000,
+      EpochUnit::Nanoseconds => 1,
+    }
+  }
+
+  /// E.g. "milliseconds"
+  pub fn name(self) -> &'static str {
+    match self {
+      EpochUnit::Seconds => "seconds",
+      EpochUnit::Milliseconds => "milliseconds",
+      EpochUnit::Microseconds => "microseconds",
+      EpochUnit::Nanoseconds => "nanoseconds",
+    }
+  }
+
+  /// Milliseconds have 13 digits, microseconds 16 and nanoseconds 19
+  /// for all instants since 2001-09-09, everything else is seconds
+  fn guess(digits: &str) -> EpochUnit {
+    let unit = match digits.len() {
+      13 => EpochUnit::Milliseconds,
+      16 => EpochUnit::Microseconds,
+      19 => EpochUnit::Nanoseconds,
+      _ => return EpochUnit::Seconds,
+    };
+    let value: i128 = digits.parse().unwrap_or_default();
+    if value * unit.nanos() / NANOS_PER_SECOND >= 1_000_000_000 {
+      unit
+    }
+    else {
+      EpochUnit::Seconds
+    }
+  }
+
+  /// The instant `value` (with optional decimal `fraction`) units
+  /// after the epoch, or before it for negative values
+  pub(crate) fn instant(
+    self,
+    negative: bool,
+    value: &str,
+    fraction: &str,
+  ) -> Option<DateTime<Utc>> {
+    from_nanos(count_nanos(negative, value, fraction, self.nanos())?)
+  }
+}
+
+impl std::str::FromStr for EpochUnit {
+  type Err = DateError;
+
+  fn from_str(s: &str) -> DateResult<EpochUnit> {
+    match s {
+      "s" | "sec" | "seconds" => Ok(EpochUnit::Seconds),
+      "ms" | "milliseconds" => Ok(EpochUnit::Milliseconds),
+      "us" | "µs" | "microseconds" => Ok(EpochUnit::Microseconds),
+      "ns" | "nanoseconds" => Ok(EpochUnit::Nanoseconds),
+      _ => date_result("expected 's', 'ms', 'us' or 'ns' as epoch unit"),
+    }
+  }
+}
+
+/// Nanoseconds of a decimal count of units with `unit_nanos` each
+fn count_nanos(
+  negative: bool,
+  value: &str,
+  fraction: &str,
+  unit_nanos: i128,
+) -> Option<i128> {
+  let mut nanos = value.parse::<i128>().ok()?.checked_mul(unit_nanos)?;
+  if !fraction.is_empty() {
+    // enough digits for nanoseconds even when counting days
+    let digits = &fraction[..fraction.len().min(18)];
+    let scale = 10_i128.pow(digits.len() as u32);
+    nanos += digits.parse::<i128>().ok()? * unit_nanos / scale;
+  }
+  Some(if negative { -nanos } else { nanos })
+}
+
+fn from_nanos(nanos: i128) -> Option<DateTime<Utc>> {
+  let seconds = i64::try_from(nanos.div_euclid(NANOS_PER_SECOND)).ok()?;
+  DateTime::from_timestamp(seconds, nanos.rem_euclid(NANOS_PER_SECOND) as u32)
+}
+
+fn to_nanos(utc: DateTime<Utc>) -> i128 {
+  i128::from(utc.timestamp()) * NANOS_PER_SECOND
+    + i128::from(utc.timestamp_subsec_nanos())
+}
+
+/// Split `[-]digits[.digits]` into sign, integer part and fraction
+fn split_decimal(number: &str) -> Option<(bool, &str, &str)> {
+  let (negative, number) = match number.strip_prefix('-') {
+    Some(number) => (true, number),
+    None => (false, number),
+  };
+  let (value, fraction) = number.split_once('.').unwrap_or((number, ""));
+  let is_digits = |s: &str| s.chars().all(|c| c.is_ascii_digit());
+  if value.is_empty()
+    || !is_digits(value)
+    || !is_digits(fraction)
+    || (number.contains('.') && fraction.is_empty())
+  {
+    return None;
+  }
+  Some((negative, value, fraction))
+}
+
+/// `nanos` as a decimal count of units with `unit_nanos` each,
+/// rounded to at most `decimals` digits after the point
+fn decimal(nanos: i128, unit_nanos: i128, decimals: u32) -> String {
+  let scale = 10_i128.pow(decimals);
+  let scaled = (nanos.abs() * scale + unit_nanos / 2) / unit_nanos;
+  let sign = if nanos < 0 && scaled != 0 { "-" } else { "" };
+  let (value, fraction) = (scaled / scale, scaled % scale);
+  if fraction == 0 {
+    return format!("{sign}{value}");
+  }
+  let fraction = format!("{fraction:0>width$}", width = decimals as usize);
+  format!("{sign}{value}.{}", fraction.trim_end_matches('0'))
+}
+
+/// Parse a timestamp like `1753438921`, `1753438921.377`,
+/// `-86400` or `@1753438921`.
+/// Without an explicit unit it's guessed from the number of digits.
+pub(crate) fn parse_epoch(
+  input: &str,
+  unit: Option<EpochUnit>,
+  trace: &mut Trace,
+) -> Option<DateTime<Utc>> {
+  let (at, number) = match input.strip_prefix('@') {
+    Some(number) => (true, number),
+    None => (false, input),
+  };
+  let (negative, value, fraction) = split_decimal(number)?;
+  // short decimals like '24.12' are left to the date parser
+  if !fraction.is_empty() && !at && unit.is_none() && value.len() <= 2 {
+    return None;
+  }
+
+  let unit = unit.unwrap_or_else(|| EpochUnit::guess(value));
+  let datetime = unit.instant(negative, value, fraction)?;
+  trace.push(format!("{} since the Unix epoch", unit.name()), &datetime);
+  Some(datetime)
+}
+
+/// Time scales which count from other epochs than the Unix one
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub(crate) enum Epoch {
+  /// Days since 1899-12-30, as used by spreadsheets
+  /// for all dates since 1900-03-01
+  Excel,
+  /// Days since noon of 4713-11-24 BC (proleptic Gregorian)
+  JulianDay,
+  /// 100 ns ticks since 1601-01-01
+  FileTime,
+  /// Seconds since 1980-01-06 without leap seconds
+  Gps,
+  /// Seconds since 2001-01-01, as used by Core Data and `NSDate`
+  Cocoa,
+  /// Seconds since 1904-01-01, as used by classic Mac OS and HFS+
+  Mac,
+}
+
+/// Instants at which a leap second was inserted since the GPS epoch
+const LEAP_SECONDS: [i64; 18] = [
+  362_793_600,   // 1981-07-01
+  394_329_600,   // 1982-07-01
+  425_865_600,   // 1983-07-01
+  489_024_000,   // 1985-07-01
+  567_993_600,   // 1988-01-01
+  631_152_000,   // 1990-01-01
+  662_688_000,   // 1991-01-01
+  709_948_800,   // 1992-07-01
+  741_484_800,   // 1993-07-01
+  773_020_800,   // 1994-07-01
+  820_454_400,   // 1996-01-01
+  867_715_200,   // 1997-07-01
+  915_148_800,   // 1999-01-01
+  1_136_073_600, // 2006-01-01
+  1_230_768_000, // 2009-01-01
+  1_341_100_800, // 2012-07-01
+  1_435_708_800, // 2015-07-01
+  1_483_228_800, // 2017-01-01
+];
+
+impl Epoch {
+  const ALL: [Epoch; 6] = [
+    Epoch::Excel,
+    Epoch::JulianDay,
+    Epoch::FileTime,
+    Epoch::Gps,
+    Epoch::Cocoa,
+    Epoch::Mac,
+  ];
+
+  /// Prefix of tagged inputs and name of the output format
+  pub(crate) fn tag(self) -> &'static str {
+    match self {
+      Epoch::Excel => "excel",
+      Epoch::JulianDay => "jd",
+      Epoch::FileTime => "filetime",
+      Epoch::Gps => "gps",
+      Epoch::Cocoa => "cocoa",
+      Epoch::Mac => "mac",
+    }
+  }
+
+  fn description(self) -> &'static str {
+    match self {
+      Epoch::Excel => "days since 1899-12-30 (Excel serial date)",
+      Epoch::JulianDay => "days since 4713-11-24 BC 12:00 (Julian day)",
+      Epoch::FileTime => "100 ns ticks since 1601-01-01 (Windows FILETIME)",
+      Epoch::Gps => "seconds since 1980-01-06 (GPS time)",
+      Epoch::Cocoa => "seconds since 2001-01-01 (Cocoa)",
+      Epoch::Mac => "seconds since 1904-01-01 (classic Mac OS)",
+    }
+  }
+
+  fn unit_nanos(self) -> i128 {
+    match self {
+      Epoch::Excel | Epoch::JulianDay => NANOS_PER_DAY,
+      Epoch::FileTime => 100,
+      Epoch::Gps | Epoch::Cocoa | Epoch::Mac => NANOS_PER_SECOND,
+    }
+  }
+
+  /// Offset of the epoch from the Unix epoch
+  fn offset_nanos(self) -> i128 {
+    let seconds: i128 = match self {
+      Epoch::Excel => -2_209_161_600,
+      Epoch::JulianDay => -210_866_760_000,
+      Epoch::FileTime => -11_644_473_600,
+      Epoch::Gps => 315_964_800,
+      Epoch::Cocoa => 978_307_200,
+      Epoch::Mac => -2_082_844_800,
+    };
+    seconds * NANOS_PER_SECOND
+  }
+
+  // digits after the decimal point when printing
+  fn decimals(self) -> u32 {
+    match self {
+      Epoch::Excel | Epoch::JulianDay => 8,
+      Epoch::FileTime => 0,
+      Epoch::Gps | Epoch::Cocoa | Epoch::Mac => 9,
+    }
+  }
+
+  fn instant(self, count: i128) -> Option<DateTime<Utc>> {
+    let mut nanos = count + self.offset_nanos();
+    if self == Epoch::Gps {
+      // GPS time runs ahead of UTC by the leap seconds since 1980
+      let leaps = LEAP_SECONDS
+        .iter()
+        .enumerate()
+        .filter(|(i, &leap)| {
+          i128::from(leap + *i as i64 + 1) * NANOS_PER_SECOND <= nanos
+        })
+        .count();
+      nanos -= leaps as i128 * NANOS_PER_SECOND;
+    }
+    from_nanos(nanos)
+  }
+
+  /// The instant as a count in this time scale
+  pub(crate) fn format(self, utc: DateTime<Utc>) -> String {
+    let mut nanos = to_nanos(utc);
+    if self == Epoch::Gps {
+      let leaps = LEAP_SECONDS
+        .iter()
+        .filter(|&&leap| leap <= utc.timestamp())
+        .count();
+      nanos += leaps as i128 * NANOS_PER_SECOND;
+    }
+    decimal(
+      nanos - self.offset_nanos(),
+      self.unit_nanos(),
+      self.decimals(),
+    )
+  }
+}
+
+/// Parse a tagged count like `excel:45678.5`, `jd:2460000.5`,
+/// `filetime:133000000000000000` or `gps:1400000000`,
+/// None if the input has no known tag
+pub(crate) fn parse_tagged(
+  input: &str,
+  trace: &mut Trace,
+) -> Option<DateResult<DateTime<Utc>>> {
+  let (tag, number) = input.split_once(':')?;
+  let epoch = Epoch::ALL
+    .into_iter()
+    .find(|epoch| epoch.tag().eq_ignore_ascii_case(tag.trim()))?;
+  let tag = epoch.tag();
+  let Some((negative, value, fraction)) = split_decimal(number.trim()) else {
+    return Some(date_result(&format!("expected a number after '{tag}:'")));
+  };
+  let datetime = count_nanos(negative, value, fraction, epoch.unit_nanos())
+    .and_then(|count| epoch.instant(count));
+  let Some(datetime) = datetime else {
+    return Some(date_result(&format!("{tag} value out of range")));
+  };
+  trace.push(epoch.description(), &datetime);
+  Some(Ok(datetime))
+}
@@ -0,0 +1,49 @@
+use std::fmt;
+
+use chrono::prelude::{DateTime, Utc};
+use serde::Serialize;
+
+use crate::chrono_english::types::{DateTimeSpec, Step};
+use crate::parser::InputKind;
+use crate::to_iso;
+
+/// How an input was parsed and resolved, as returned by
+/// [`Parser::explain`](crate::Parser::explain)
+#[derive(Debug, Clone, Serialize)]
+pub struct Explanation {
+  /// The input as given
+  pub input: String,
+  /// The input after expanding aliases and removing leading "in"/"at" etc.
+  pub normalized: String,
+  /// The rule which recognized the input
+  pub kind: InputKind,
+  /// The parse tree for natural language expressions
+  pub spec: Option<DateTimeSpec>,
+  /// Each step from the reference time to the final instant
+  pub steps: Vec<Step>,
+  /// The resolved instant
+  pub utc: DateTime<Utc>,
+}
+
+impl fmt::Display for Explanation {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    writeln!(f, "Input:      {}", self.input)?;
+    writeln!(f, "Normalized: {}", self.normalized)?;
+    writeln!(f, "Kind:       {:?}", self.kind)?;
+    if let Some(spec) = &self.spec {
+      match &spec.date {
+        Some(date) => writeln!(f, "Date:       {date}")?,
+        None => writeln!(f, "Date:       none")?,
+      }
+      match &spec.time {
+        Some(time) => writeln!(f, "Time:       {time}")?,
+        None => writeln!(f, "Time:       none")?,
+      }
+    }
+    writeln!(f, "Steps:")?;
+    for (i, step) in self.steps.iter().enumerate() {
+      writeln!(f, "  {}. {} -> {}", i + 1, step.action, step.value)?;
+    }
+    write!(f, "Result:     {}", to_iso(self.utc))
+  }
+}
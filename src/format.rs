@@ -0,0 +1,75 @@
+use crate::chrono_english::lib::{date_result, DateError, DateResult};
+use crate::epoch::Epoch;
+use crate::parser::ParsedDate;
+
+/// How a resolved instant is printed
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq)]
+pub enum Format {
+  /// RFC 3339 in UTC with a `Z` suffix (default)
+  #[default]
+  Iso,
+  /// RFC 3339 in the zone of the parser
+  Local,
+  /// RFC 2822 in the zone of the parser
+  Rfc2822,
+  /// Seconds since the Unix epoch
+  Unix,
+  /// Milliseconds since the Unix epoch
+  UnixMillis,
+  /// Days since 1899-12-30 as used by spreadsheets
+  Excel,
+  /// Julian day
+  JulianDay,
+  /// 100 ns ticks since 1601-01-01 (Windows FILETIME)
+  FileTime,
+  /// GPS seconds since 1980-01-06
+  Gps,
+  /// Seconds since 2001-01-01 (Cocoa)
+  Cocoa,
+  /// Seconds since 1904-01-01 (classic Mac OS)
+  Mac,
+}
+
+impl std::str::FromStr for Format {
+  type Err = DateError;
+
+  fn from_str(s: &str) -> DateResult<Format> {
+    match s {
+      "iso" | "rfc3339" => Ok(Format::Iso),
+      "local" => Ok(Format::Local),
+      "rfc2822" => Ok(Format::Rfc2822),
+      "unix" => Ok(Format::Unix),
+      "unix-ms" => Ok(Format::UnixMillis),
+      "excel" => Ok(Format::Excel),
+      "jd" => Ok(Format::JulianDay),
+      "filetime" => Ok(Format::FileTime),
+      "gps" => Ok(Format::Gps),
+      "cocoa" => Ok(Format::Cocoa),
+      "mac" => Ok(Format::Mac),
+      _ => date_result(
+        "expected 'iso', 'local', 'rfc2822', 'unix', 'unix-ms', 'excel', \
+         'jd', 'filetime', 'gps', 'cocoa' or 'mac' as format",
+      ),
+    }
+  }
+}
+
+impl ParsedDate {
+  /// Print the instant in the given format
+  pub fn format(&self, format: Format) -> String {
+    match format {
+      Format::Iso => self.to_iso(),
+      Format::Local => self.local.to_rfc3339(),
+      Format::Rfc2822 => self.local.to_rfc2822(),
+      Format::Unix => self.utc.timestamp().to_string(),
+      Format::UnixMillis => self.utc.timestamp_millis().to_string(),
+      Format::Excel => Epoch::Excel.format(self.utc),
+      Format::JulianDay => Epoch::JulianDay.format(self.utc),
+      Format::FileTime => Epoch::FileTime.format(self.utc),
+      Format::Gps => Epoch::Gps.format(self.utc),
+      Format::Cocoa => Epoch::Cocoa.format(self.utc),
+      Format::Mac => Epoch::Mac.format(self.utc),
+    }
+  }
+}
@@ -0,0 +1,228 @@
+use chrono::prelude::Datelike;
+
+use crate::chrono_english::lib::{
+  Ambiguity, DateError, Dialect, DirectionPolicy,
+};
+use crate::epoch::EpochUnit;
+use crate::parser::{InputKind, ParsedDate, Parser};
+
+/// One reading of a possibly ambiguous input,
+/// as returned by [`Parser::interpretations`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct Interpretation {
+  /// 1 for the reading which [`Parser::parse`] returns,
+  /// higher numbers for less likely ones
+  pub rank: usize,
+  /// The rule which produced this reading
+  pub rule: String,
+  pub date: ParsedDate,
+}
+
+/// Readings of an input which only make sense as alternatives
+type Group = Vec<(String, Option<ParsedDate>)>;
+
+impl Parser {
+  /// All plausible readings of an input, ranked by likelihood.
+  ///
+  /// Covers numbers which could be timestamps or dates,
+  /// the order of day and month in numeric dates,
+  /// the meaning of "next" and "last",
+  /// and local times repeated by daylight saving transitions.
+  pub fn interpretations(
+    &self,
+    input: &str,
+  ) -> Result<Vec<Interpretation>, DateError> {
+    let primary = self.parse(input);
+    let alternatives = self.alternatives(input.trim());
+
+    let mut interpretations: Vec<Interpretation> = Vec::new();
+    if let Ok(date) = &primary {
+      let rule = alternatives
+        .iter()
+        .find(|(_, alternative)| alternative.utc == date.utc)
+        .map(|(rule, _)| rule.clone())
+        .unwrap_or_else(|| describe(date.kind).to_string());
+      interpretations.push(Interpretation {
+        rank: 1,
+        rule,
+        date: date.clone(),
+      });
+    }
+    for (rule, date) in alternatives {
+      if interpretations
+        .iter()
+        .all(|known| known.date.utc != date.utc)
+      {
+        interpretations.push(Interpretation {
+          rank: interpretations.len() + 1,
+          rule,
+          date,
+        });
+      }
+    }
+
+    match primary {
+      Err(e) if interpretations.is_empty() => Err(e),
+      _ => Ok(interpretations),
+    }
+  }
+
+  fn alternatives(&self, input: &str) -> Vec<(String, ParsedDate)> {
+    let mut groups: Vec<Group> = Vec::new();
+
+    if !input.is_empty() && input.chars().all(|c| c.is_ascii_digit()) {
+      groups.push(self.number_readings(input));
+    }
+
+    // '03/04/05' or '9/11 10:00'
+    let (first, rest) = input.split_once(' ').unwrap_or((input, ""));
+    let parts: Vec<&str> = first.split('/').collect();
+    let numeric = parts
+      .iter()
+      .all(|part| !part.is_empty() && part.chars().all(|c| c.is_ascii_digit()));
+    if numeric && (parts.len() == 2 || parts.len() == 3) {
+      let mut group = vec![
+        (
+          "month/day (US)".to_string(),
+          self.clone().dialect(Dialect::Us).parse(input).ok(),
+        ),
+        (
+          "day/month (UK)".to_string(),
+          self.clone().dialect(Dialect::Uk).parse(input).ok(),
+        ),
+      ];
+      if let [year, month, day] = parts[..] {
+        if year.len() == 2 {
+          let year: u32 = year.parse().unwrap_or_default();
+          // same pivot as for 'day/month/year'
+          let year = if year > 40 { 1900 + year } else { 2000 + year };
+          let iso = format!("{year}-{month:0>2}-{day:0>2} {rest}");
+          group
+            .push(("year/month/day".to_string(), self.parse(iso.trim()).ok()));
+        }
+      }
+      groups.push(group);
+    }
+
+    let language = self.options.language;
+    let directed = input
+      .split_whitespace()
+      .any(|word| language.direction(&word.to_lowercase()).is_some());
+    if directed {
+      groups.push(vec![
+        (
+          "next/last as the closest match".to_string(),
+          self
+            .clone()
+            .direction_policy(DirectionPolicy::Closest)
+            .parse(input)
+            .ok(),
+        ),
+        (
+          "next/last as the following/previous week or year".to_string(),
+          self
+            .clone()
+            .direction_policy(DirectionPolicy::Period)
+            .parse(input)
+            .ok(),
+        ),
+      ]);
+    }
+
+    groups.push(vec![
+      (
+        "earlier instant of a repeated local time".to_string(),
+        self
+          .clone()
+          .ambiguity(Ambiguity::Earliest)
+          .parse(input)
+          .ok(),
+      ),
+      (
+        "later instant of a repeated local time".to_string(),
+        self.clone().ambiguity(Ambiguity::Latest).parse(input).ok(),
+      ),
+    ]);
+
+    groups
+      .into_iter()
+      .filter(|group| {
+        // only readings which differ from each other are alternatives
+        let mut instants: Vec<_> = group
+          .iter()
+          .flat_map(|(_, date)| date)
+          .map(|d| d.utc)
+          .collect();
+        instants.sort();
+        instants.dedup();
+        instants.len() > 1
+      })
+      .flatten()
+      .filter_map(|(rule, date)| date.map(|date| (rule, date)))
+      .collect()
+  }
+
+  // a number can be a timestamp, but also an hour, a day or a year
+  fn number_readings(&self, digits: &str) -> Group {
+    let n: i64 = digits.parse().unwrap_or_default();
+    let epoch = |unit: EpochUnit| {
+      (
+        format!("{} since the Unix epoch", unit.name()),
+        unit
+          .instant(false, digits, "")
+          .map(|utc| ParsedDate::new(utc, self.zone, InputKind::UnixTimestamp)),
+      )
+    };
+    let mut group = vec![epoch(EpochUnit::Seconds)];
+    let mut natural = |rule: &str, expression: String| {
+      group.push((rule.to_string(), self.parse(&expression).ok()));
+    };
+    let today = self.reference_time().with_timezone(&self.zone).date_naive();
+    match digits.len() {
+      1 | 2 => {
+        if n <= 24 {
+          natural("hour of today", format!("{n}:00"));
+        }
+        if let Some(day) = today.with_day(n as u32) {
+          natural("day of this month", day.format("%Y-%m-%d").to_string());
+        }
+      }
+      4 => natural("year", format!("{digits}-01-01")),
+      8 => natural(
+        "year, month and day",
+        format!("{}-{}-{}", &digits[..4], &digits[4..6], &digits[6..]),
+      ),
+      10 => natural(
+        "year, month, day and hour",
+        format!(
+          "{}-{}-{} {}:00",
+          &digits[..4],
+          &digits[4..6],
+          &digits[6..8],
+          &digits[8..]
+        ),
+      ),
+      _ => (),
+    }
+    group.push(epoch(EpochUnit::Milliseconds));
+    if digits.len() > 13 {
+      group.push(epoch(EpochUnit::Microseconds));
+      group.push(epoch(EpochUnit::Nanoseconds));
+    }
+    group
+  }
+}
+
+// name of the rule for inputs without alternatives
+fn describe(kind: InputKind) -> &'static str {
+  match kind {
+    InputKind::UnixTimestamp => "Unix timestamp",
+    InputKind::Serial => "serial date",
+    InputKind::Rfc2822 => "RFC 2822",
+    InputKind::Rfc3339 => "RFC 3339",
+    InputKind::Layout => "log or programming language timestamp",
+    InputKind::Pattern => "input format",
+    InputKind::Natural => "natural language expression",
+    InputKind::NamedDate => "named date",
+  }
+}
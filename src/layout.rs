@@ -0,0 +1,235 @@
+use chrono::prelude::{
+  DateTime, Datelike, FixedOffset, NaiveDate, NaiveDateTime, TimeZone, Utc,
+};
+use chrono_tz::Tz;
+
+use crate::chrono_english::lib::{Ambiguity, Bias};
+use crate::chrono_english::types::{from_local, Trace};
+
+/// Where the offset of a layout comes from
+#[derive(Debug, Clone, Copy)]
+enum Offset {
+  /// Part of the input, e.g. `-0700`
+  Given,
+  /// Always UTC, e.g. `GMT` in HTTP dates
+  Utc,
+  /// Not given, so the zone of the parser applies
+  Local,
+}
+
+/// A fixed timestamp layout in `strftime` syntax
+struct Layout {
+  name: &'static str,
+  format: &'static str,
+  offset: Offset,
+}
+
+const LAYOUTS: [Layout; 5] = [
+  // 10/Oct/2000:13:55:36 -0700
+  Layout {
+    name: "Apache/NGINX log time",
+    format: "%d/%b/%Y:%H:%M:%S %z",
+    offset: Offset::Given,
+  },
+  // HTTP-dates like 'Sun, 06 Nov 1994 08:49:37 GMT' are RFC 2822,
+  // but the obsolete forms of RFC 850 and asctime are allowed as well.
+  // Sunday, 06-Nov-94 08:49:37 GMT
+  Layout {
+    name: "RFC 850",
+    format: "%A, %d-%b-%y %H:%M:%S GMT",
+    offset: Offset::Utc,
+  },
+  // Wed Jun 30 21:49:08 1993
+  Layout {
+    name: "asctime",
+    format: "%a %b %e %H:%M:%S %Y",
+    offset: Offset::Local,
+  },
+  // 2006-01-02 15:04:05.999999999 -0700 MST
+  Layout {
+    name: "Go time",
+    format: "%Y-%m-%d %H:%M:%S%.f %z %Z",
+    offset: Offset::Given,
+  },
+  // 2024-01-15 10:30:45,123
+  Layout {
+    name: "Python logging time",
+    format: "%Y-%m-%d %H:%M:%S,%3f",
+    offset: Offset::Local,
+  },
+];
+
+/// Recognize timestamps as printed by logs and programming languages,
+/// like `[10/Oct/2000:13:55:36 -0700]`, `Oct 10 13:55:36`
+/// or `datetime.datetime(2024, 1, 15, 10, 30)`
+pub(crate) fn parse_layout(
+  input: &str,
+  now: DateTime<Tz>,
+  ambiguity: Ambiguity,
+  bias: Option<Bias>,
+  trace: &mut Trace,
+) -> Option<DateTime<Utc>> {
+  let input = input
+    .strip_prefix('[')
+    .and_then(|rest| rest.strip_suffix(']'))
+    .unwrap_or(input)
+    .trim();
+  // Go appends the monotonic clock reading, e.g. 'm=+0.000064301'
+  let input = match input.rsplit_once(" m=") {
+    Some((time, _)) => time,
+    None => input,
+  };
+  let zone = now.timezone();
+
+  for layout in &LAYOUTS {
+    let datetime = match layout.offset {
+      Offset::Given => DateTime::parse_from_str(input, layout.format)
+        .ok()
+        .map(|datetime| datetime.with_timezone(&Utc)),
+      Offset::Utc => NaiveDateTime::parse_from_str(input, layout.format)
+        .ok()
+        .map(|naive| naive.and_utc()),
+      Offset::Local => NaiveDateTime::parse_from_str(input, layout.format)
+        .ok()
+        .and_then(|naive| from_local(&zone, &naive, ambiguity))
+        .map(|datetime| datetime.with_timezone(&Utc)),
+    };
+    if let Some(datetime) = datetime {
+      trace.push(format!("parsed as {}", layout.name), &datetime);
+      return Some(datetime);
+    }
+  }
+
+  if let Some(datetime) = parse_syslog(input, now, ambiguity, bias) {
+    let year = match bias {
+      Some(bias) => format!("by bias {bias:?}"),
+      None => "of the last 12 months".to_string(),
+    };
+    trace.push(format!("parsed as syslog time {year}"), &datetime);
+    return Some(datetime);
+  }
+  if let Some(datetime) = parse_python_repr(input, &zone, ambiguity) {
+    trace.push("parsed as Python datetime", &datetime);
+    return Some(datetime);
+  }
+  None
+}
+
+// 'Oct 10 13:55:36' has no year, so it's the most recent such time
+// unless a bias says otherwise
+fn parse_syslog(
+  input: &str,
+  now: DateTime<Tz>,
+  ambiguity: Ambiguity,
+  bias: Option<Bias>,
+) -> Option<DateTime<Utc>> {
+  let zone = now.timezone();
+  let in_year = |year: i32| {
+    let naive = NaiveDateTime::parse_from_str(
+      &format!("{year} {input}"),
+      "%Y %b %e %H:%M:%S%.f",
+    )
+    .ok()?;
+    from_local(&zone, &naive, ambiguity)
+  };
+  if let Some(bias) = bias {
+    // enough years to find a Feb 29
+    let times: Vec<_> = (now.year() - 4..=now.year() + 4)
+      .filter_map(in_year)
+      .map(|datetime| (datetime, datetime))
+      .collect();
+    let i = bias.choose(&times, &now)?;
+    return Some(times[i].0.with_timezone(&Utc));
+  }
+  // logs written shortly before 'now' on another machine
+  // may be a little ahead
+  let datetime = in_year(now.year())?;
+  let datetime = if datetime > now + chrono::Duration::days(1) {
+    in_year(now.year() - 1)?
+  }
+  else {
+    datetime
+  };
+  Some(datetime.with_timezone(&Utc))
+}
+
+// datetime.datetime(2024, 1, 15, 10, 30, 45, 123456, tzinfo=...)
+fn parse_python_repr(
+  input: &str,
+  zone: &Tz,
+  ambiguity: Ambiguity,
+) -> Option<DateTime<Utc>> {
+  let args = input
+    .strip_prefix("datetime.")
+    .unwrap_or(input)
+    .strip_prefix("datetime(")
+    .or_else(|| input.strip_prefix("datetime.date("))?
+    .strip_suffix(')')?;
+  let (args, tzinfo) = match args.split_once("tzinfo=") {
+    Some((args, tzinfo)) => (args, Some(tzinfo.trim())),
+    None => (args, None),
+  };
+  let numbers = args
+    .split(',')
+    .map(str::trim)
+    .filter(|arg| !arg.is_empty())
+    .map(|arg| arg.parse::<u32>().ok())
+    .collect::<Option<Vec<u32>>>()?;
+  if !(3..=7).contains(&numbers.len()) {
+    return None;
+  }
+  let field = |i: usize| numbers.get(i).copied().unwrap_or_default();
+  let naive = NaiveDate::from_ymd_opt(field(0) as i32, field(1), field(2))?
+    .and_hms_micro_opt(field(3), field(4), field(5), field(6))?;
+
+  let datetime = match tzinfo {
+    None => from_local(zone, &naive, ambiguity)?.with_timezone(&Utc),
+    Some(tzinfo) => match python_zone(tzinfo)? {
+      PythonZone::Named(zone) => {
+        from_local(&zone, &naive, ambiguity)?.with_timezone(&Utc)
+      }
+      PythonZone::Fixed(offset) => offset
+        .from_local_datetime(&naive)
+        .single()?
+        .with_timezone(&Utc),
+    },
+  };
+  Some(datetime)
+}
+
+enum PythonZone {
+  Named(Tz),
+  Fixed(FixedOffset),
+}
+
+// 'datetime.timezone.utc', "zoneinfo.ZoneInfo(key='Europe/Berlin')",
+// "<DstTzInfo 'Europe/Berlin' CET+1:00:00 STD>"
+// or 'datetime.timezone(datetime.timedelta(seconds=3600))'
+fn python_zone(tzinfo: &str) -> Option<PythonZone> {
+  if let Some(quoted) = tzinfo.split(['\'', '"']).nth(1) {
+    return quoted.parse().ok().map(PythonZone::Named);
+  }
+  if let Some((_, timedelta)) = tzinfo.split_once("timedelta(") {
+    let (timedelta, _) = timedelta.split_once(')')?;
+    let mut seconds = 0;
+    for (i, arg) in timedelta.split(',').map(str::trim).enumerate() {
+      let (unit, value) = arg
+        .split_once('=')
+        .unwrap_or((["days", "seconds"].get(i)?, arg));
+      let value: i32 = value.trim().parse().ok()?;
+      seconds += value
+        * match unit.trim() {
+          "days" => 86_400,
+          "hours" => 3_600,
+          "minutes" => 60,
+          "seconds" => 1,
+          _ => return None,
+        };
+    }
+    return FixedOffset::east_opt(seconds).map(PythonZone::Fixed);
+  }
+  tzinfo
+    .to_lowercase()
+    .contains("utc")
+    .then_some(PythonZone::Named(Tz::UTC))
+}
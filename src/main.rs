@@ -1,40 +1,796 @@
-use chrono::prelude::Utc;
-use tu::{parse_date_args, parse_print, to_iso};
+use std::ffi::OsStr;
+use std::io::{BufRead, Write};
+use std::path::PathBuf;
+use std::str::FromStr;
 
-/// Parse arguments and convert to UTC timestamp
-fn main() {
-  let args: Vec<String> = std::env::args().collect();
-  let now = Utc::now();
-
-  if args.len() < 2 {
-    let s = "  ";
-    let cmd = &args[0];
-    let today = parse_print(now, "today");
-    let tomorrow = parse_print(now, "tomorrow");
-    let day2 = parse_print(now, "2 days");
-    let week9 = parse_print(now, "9 weeks");
-    let month1 = parse_print(now, "1 month");
-
-    eprintln!(
-      "Usage: {cmd} <natural time/duration> \n\
-            \n\
-            Examples:\n\
-            {s}{cmd} today      -> {today}\n\
-            {s}{cmd} tomorrow   -> {tomorrow}\n\
-            {s}{cmd} 2 day      -> {day2}\n\
-            {s}{cmd} 9 week     -> {week9}\n\
-            {s}{cmd} 1 month    -> {month1}\n\
-            \n\
-            {s}{cmd} 2024-04-10T13:31:46+04:00     -> 2024-04-10T09:31:46Z\n\
-            {s}{cmd} Wed, 14 Feb 2024 23:16:09 GMT -> 2024-02-14T23:16:09Z\n\
-            "
+use chrono::prelude::{DateTime, NaiveTime, Utc};
+use clap::builder::{
+  PossibleValue, PossibleValuesParser, StringValueParser, TypedValueParser,
+};
+use clap::{Args, CommandFactory, Parser as _, Subcommand};
+use clap_complete::Shell;
+use tu::language::{Language, ENGLISH, LANGUAGES};
+use tu::{
+  common_working_hours, humanize, iso_duration, Bias, Config, DateError,
+  DateRange, Dialect, DirectionPolicy, EpochUnit, Format, ParsedDate, Parser,
+  Rounding, Session, TimePolicy, TimeUnit, Tz, WeekStart, WorkingHours,
+  ZoneInfo,
+};
+
+const EXAMPLES: &str = "\
+Examples:
+  tu tomorrow 4pm
+  tu --zone Europe/Berlin next friday
+  tu -- -2d
+  tu diff \"monday 9am\" \"friday 17:00\"
+  tu humanize 2024-04-10T13:31:46+04:00
+  tu completions fish > ~/.config/fish/completions/tu.fish";
+
+/// Convert natural language dates and times to UTC
+#[derive(clap::Parser)]
+#[command(
+  name = "tu",
+  version,
+  override_usage = "tu [OPTIONS] [EXPRESSION]...\n       tu [OPTIONS] <COMMAND>",
+  subcommand_negates_reqs = true,
+  arg_required_else_help = true,
+  after_help = EXAMPLES
+)]
+struct Cli {
+  #[command(flatten)]
+  settings: Settings,
+  #[command(flatten)]
+  parse: ParseArgs,
+  #[command(subcommand)]
+  command: Option<Command>,
+}
+
+/// How expressions are read, for all subcommands
+#[derive(Args)]
+struct Settings {
+  /// Config file (default: ~/.config/tu/config.toml)
+  #[arg(long, global = true, value_name = "PATH")]
+  config: Option<PathBuf>,
+  /// Zone of the expression (default: UTC)
+  #[arg(long, global = true, value_name = "IANA NAME", value_parser = zone)]
+  zone: Option<Tz>,
+  /// Zone deciding abbreviations like IST or CST (repeatable)
+  #[arg(
+    long = "prefer-zone",
+    global = true,
+    value_name = "IANA NAME",
+    value_parser = zone
+  )]
+  prefer_zones: Vec<Tz>,
+  /// Order of day and month in 9/11
+  #[arg(long, global = true, value_parser = one_of::<Dialect>(["us", "uk"]))]
+  dialect: Option<Dialect>,
+  /// Output format (default: iso)
+  #[arg(long, global = true, value_parser = one_of::<Format>([
+    PossibleValue::new("iso").alias("rfc3339"),
+    "local".into(), "rfc2822".into(), "unix".into(), "unix-ms".into(),
+    "excel".into(), "jd".into(), "filetime".into(), "gps".into(),
+    "cocoa".into(), "mac".into(),
+  ]))]
+  format: Option<Format>,
+  /// Meaning of next/this/last friday, march, …
+  #[arg(
+    long,
+    global = true,
+    value_parser = one_of::<DirectionPolicy>(["closest", "period"])
+  )]
+  direction: Option<DirectionPolicy>,
+  /// Time of day of dates without one, like tomorrow or friday
+  /// (default: preserve for relative dates, start-of-day for others)
+  #[arg(
+    long = "time-policy",
+    global = true,
+    value_parser = one_of::<TimePolicy>(["preserve", "start-of-day"])
+  )]
+  time_policy: Option<TimePolicy>,
+  /// Occurrence of friday, march, 5 may or dates without a year
+  /// (default: the next weekday, or in the current year)
+  #[arg(
+    long,
+    global = true,
+    value_parser = one_of::<Bias>(["past", "future", "nearest"])
+  )]
+  prefer: Option<Bias>,
+  /// First day of the week (default: monday)
+  #[arg(long = "week-start", global = true, value_parser = one_of::<WeekStart>([
+    PossibleValue::new("monday").alias("mon"),
+    PossibleValue::new("sunday").alias("sun"),
+    PossibleValue::new("saturday").alias("sat"),
+  ]))]
+  week_start: Option<WeekStart>,
+  /// Define a named time like morning=08:00 (repeatable)
+  #[arg(
+    long = "time-of-day",
+    global = true,
+    value_name = "NAME=HH:MM",
+    value_parser = time_of_day
+  )]
+  times_of_day: Vec<(String, NaiveTime)>,
+  /// Language of the expression (default: en)
+  #[arg(
+    long,
+    global = true,
+    value_parser = PossibleValuesParser::new(LANGUAGES.map(|lang| lang.code()))
+      .map(|code| tu::language::by_code(&code).unwrap())
+  )]
+  lang: Option<&'static dyn Language>,
+  /// Parse the expression with a strptime pattern like %d.%m.%Y %H%M (repeatable)
+  #[arg(long = "input-format", global = true, value_name = "PATTERN")]
+  input_formats: Vec<String>,
+  /// Unit of numeric timestamps (default: by digits)
+  #[arg(long = "epoch-unit", global = true, value_parser = one_of::<EpochUnit>([
+    PossibleValue::new("s").aliases(["sec", "seconds"]),
+    "ms".into(),
+    PossibleValue::new("us").alias("µs"),
+    "ns".into(),
+  ]))]
+  epoch_unit: Option<EpochUnit>,
+  /// Round the result to the closest start of a unit
+  #[arg(long, global = true, value_name = "UNIT", value_parser = unit())]
+  round: Option<TimeUnit>,
+  /// Move the result back to the start of its unit, like midnight for day
+  #[arg(
+    long,
+    global = true,
+    value_name = "UNIT",
+    value_parser = unit(),
+    conflicts_with = "round"
+  )]
+  floor: Option<TimeUnit>,
+  /// Move the result forward to the start of the next unit
+  #[arg(
+    long,
+    global = true,
+    value_name = "UNIT",
+    value_parser = unit(),
+    conflicts_with_all = ["round", "floor"]
+  )]
+  ceil: Option<TimeUnit>,
+  /// Reference time instead of the clock, also TU_NOW (repeatable)
+  #[arg(long, global = true, value_name = "EXPRESSION")]
+  now: Vec<String>,
+}
+
+/// Options of the default command, which resolves an expression
+#[derive(Args)]
+struct ParseArgs {
+  /// Resolve expressions line by line, see :help
+  #[arg(long, short)]
+  interactive: bool,
+  /// Show how the expression was parsed and resolved
+  #[arg(long)]
+  explain: bool,
+  /// List all interpretations of an ambiguous expression
+  #[arg(long)]
+  all: bool,
+  /// Show the result in the zones of the config file
+  #[arg(long = "show-zones")]
+  show_zones: bool,
+  /// Show the result in this zone (repeatable)
+  #[arg(long = "show-zone", value_name = "IANA NAME", value_parser = zone)]
+  shown_zones: Vec<Tz>,
+  /// Print the zones shown as JSON
+  #[arg(long)]
+  json: bool,
+  /// Natural time or duration like "next friday 4pm" or "-2d"
+  #[arg(
+    value_name = "EXPRESSION",
+    required_unless_present = "interactive",
+    allow_hyphen_values = true,
+    hide_possible_values = true,
+    value_parser = Vocabulary(&ENGLISH)
+  )]
+  expression: Vec<String>,
+}
+
+#[derive(Args)]
+struct Expression {
+  /// Natural time or duration like "next friday 4pm" or "-2d"
+  #[arg(
+    value_name = "EXPRESSION",
+    required = true,
+    allow_hyphen_values = true,
+    hide_possible_values = true,
+    value_parser = Vocabulary(&ENGLISH)
+  )]
+  expression: Vec<String>,
+}
+
+impl Expression {
+  fn text(&self) -> String {
+    text(&self.expression)
+  }
+}
+
+// the words of an expression, which may start with '-' like -2d,
+// so options after it end up among the words
+fn text(words: &[String]) -> String {
+  if let Some(i) = words.iter().position(|word| word.starts_with("--")) {
+    let tip = if i > 0 {
+      "\n\n  tip: options go before the expression"
+    }
+    else {
+      ""
+    };
+    Cli::command()
+      .error(
+        clap::error::ErrorKind::UnknownArgument,
+        format!("unexpected argument '{}' found{tip}", words[i]),
+      )
+      .exit();
+  }
+  words.join(" ")
+}
+
+// the zone and expression of 'convert tomorrow 9am --to Tokyo',
+// where the expression took the --to following it
+fn convert_args(to: Option<Tz>, words: &[String]) -> (Tz, String) {
+  let error = |kind, message: String| -> ! {
+    let mut cli = Cli::command();
+    cli.build();
+    cli
+      .find_subcommand_mut("convert")
+      .unwrap()
+      .error(kind, message)
+      .exit()
+  };
+  let mut words = words.to_vec();
+  let mut to = to;
+  if let Some(i) = words
+    .iter()
+    .position(|word| word == "--to" || word.starts_with("--to="))
+  {
+    let word = words.remove(i);
+    let value = match word.strip_prefix("--to=") {
+      Some(value) => value.to_string(),
+      None if i < words.len() => words.remove(i),
+      None => error(
+        clap::error::ErrorKind::InvalidValue,
+        "a value is required for '--to <IANA NAME|CITY>' but none was supplied"
+          .to_string(),
+      ),
+    };
+    to = Some(zone(&value).unwrap_or_else(|e| {
+      error(
+        clap::error::ErrorKind::ValueValidation,
+        format!("invalid value '{value}' for '--to <IANA NAME|CITY>': {e}"),
+      )
+    }));
+  }
+  let Some(to) = to else {
+    error(
+      clap::error::ErrorKind::MissingRequiredArgument,
+      "the following required arguments were not provided:\n  \
+       --to <IANA NAME|CITY>"
+        .to_string(),
+    )
+  };
+  (to, text(&words))
+}
+
+#[derive(Subcommand)]
+enum Command {
+  /// Resolve an expression to an instant (the default)
+  Parse(ParseArgs),
+  /// The time from now until an instant, as an ISO 8601 duration
+  Duration(Expression),
+  /// The time between two instants, as an ISO 8601 duration
+  Diff {
+    /// E.g. "monday 9am"
+    #[arg(
+      allow_hyphen_values = true,
+      hide_possible_values = true,
+      value_parser = Vocabulary(&ENGLISH)
+    )]
+    from: String,
+    /// E.g. "friday 17:00"
+    #[arg(
+      allow_hyphen_values = true,
+      hide_possible_values = true,
+      value_parser = Vocabulary(&ENGLISH)
+    )]
+    to: String,
+  },
+  /// The day, week, month or year of an expression, as an ISO 8601 interval
+  Range(Expression),
+  /// An instant relative to now in words, like "in 3 days"
+  Humanize(Expression),
+  /// List the zones whose name or abbreviation contains the filter
+  Zones {
+    filter: Vec<String>,
+    /// Instant to show the zones at (default: now)
+    #[arg(long, value_name = "EXPRESSION")]
+    at: Option<String>,
+  },
+  /// Show a zone, or the zones using an abbreviation
+  Zone {
+    #[arg(value_name = "IANA NAME|ABBREVIATION")]
+    name: String,
+    /// Instant to show the zone at (default: now)
+    #[arg(long, value_name = "EXPRESSION")]
+    at: Option<String>,
+  },
+  /// Find the times within the working hours of all zones
+  Overlap {
+    /// Comma separated zones
+    #[arg(
+      long,
+      required = true,
+      value_delimiter = ',',
+      value_name = "IANA NAMES",
+      value_parser = zone
+    )]
+    zones: Vec<Tz>,
+    /// Working hours like 9-17 or 08:30-16:30, from Monday to Friday
+    #[arg(long, default_value = "9-17", value_parser = parse::<WorkingHours>)]
+    hours: WorkingHours,
+    /// Day, week or month to find working hours in
+    #[arg(long, default_value = "this week", value_name = "PERIOD")]
+    on: String,
+  },
+  /// Show an instant in another zone (default format: local)
+  Convert {
+    /// Zone to convert to, also after the expression (required)
+    #[arg(long, value_name = "IANA NAME|CITY", value_parser = zone)]
+    to: Option<Tz>,
+    #[command(flatten)]
+    expression: Expression,
+  },
+  /// Print completions for a shell
+  Completions { shell: Shell },
+}
+
+/// Accepts any word,
+/// and suggests the vocabulary of a language for shell completions
+#[derive(Clone)]
+struct Vocabulary(&'static dyn Language);
+
+impl TypedValueParser for Vocabulary {
+  type Value = String;
+
+  fn parse_ref(
+    &self,
+    command: &clap::Command,
+    arg: Option<&clap::Arg>,
+    value: &OsStr,
+  ) -> Result<String, clap::Error> {
+    StringValueParser::new().parse_ref(command, arg, value)
+  }
+
+  fn possible_values(
+    &self,
+  ) -> Option<Box<dyn Iterator<Item = PossibleValue> + '_>> {
+    Some(Box::new(
+      self.0.vocabulary().into_iter().map(PossibleValue::new),
+    ))
+  }
+}
+
+// the names of `T` for completions and help
+fn one_of<T>(
+  names: impl IntoIterator<Item = impl Into<PossibleValue>>,
+) -> impl TypedValueParser<Value = T>
+where
+  T: FromStr<Err = DateError> + Clone + Send + Sync + 'static,
+{
+  PossibleValuesParser::new(names).try_map(|name| name.parse::<T>())
+}
+
+fn unit() -> impl TypedValueParser<Value = TimeUnit> {
+  one_of([
+    PossibleValue::new("second").aliases(["s", "sec", "seconds"]),
+    PossibleValue::new("minute").aliases(["min", "minutes"]),
+    PossibleValue::new("hour").aliases(["h", "hours"]),
+    PossibleValue::new("day").aliases(["d", "days"]),
+    PossibleValue::new("week").aliases(["w", "weeks"]),
+    PossibleValue::new("month").alias("months"),
+    PossibleValue::new("year").aliases(["y", "years"]),
+  ])
+}
+
+fn parse<T: FromStr<Err = DateError>>(value: &str) -> Result<T, DateError> {
+  value.parse()
+}
+
+fn zone(name: &str) -> Result<Tz, String> {
+  ZoneInfo::find(name).ok_or_else(|| format!("unknown zone '{name}'"))
+}
+
+fn time_of_day(value: &str) -> Result<(String, NaiveTime), String> {
+  value
+    .split_once('=')
+    .and_then(|(name, time)| {
+      let time = NaiveTime::parse_from_str(time, "%H:%M").ok()?;
+      Some((name.to_string(), time))
+    })
+    .ok_or_else(|| "expected <name>=<HH:MM>".to_string())
+}
+
+fn exit_with_error(message: impl std::fmt::Display) -> ! {
+  eprintln!("ERROR:\n{message}");
+  std::process::exit(1);
+}
+
+// the instant given with --at, read in the zone of `parser`
+fn instant(parser: &Parser, at: Option<&str>) -> DateTime<Utc> {
+  match at {
+    Some(at) => parser.parse(at).unwrap_or_else(|e| exit_with_error(e)).utc,
+    None => parser.reference_time(),
+  }
+}
+
+fn print_zones(parser: &Parser, filter: &str, at: Option<&str>) {
+  let zones = ZoneInfo::list(filter, instant(parser, at));
+  if zones.is_empty() {
+    exit_with_error(format!("no zone matches '{filter}'"));
+  }
+  let width = zones.iter().map(|info| info.zone.name().len()).max();
+  for info in zones {
+    println!(
+      "{:width$}  {}  {}{}",
+      info.zone.name(),
+      info.offset,
+      info.abbreviation,
+      if info.dst { " (DST)" } else { "" },
+      width = width.unwrap_or_default(),
     );
-    std::process::exit(1);
   }
+}
+
+fn print_zone(parser: &Parser, name: &str, at: Option<&str>) {
+  if let Some(zone) = ZoneInfo::find(name) {
+    let utc = instant(&parser.clone().zone(zone), at);
+    println!("{}", ZoneInfo::at(zone, utc));
+    return;
+  }
+  let uses = ZoneInfo::using_abbreviation(name);
+  if uses.is_empty() {
+    exit_with_error(format!("unknown zone or abbreviation '{name}'"));
+  }
+  println!("{} is used by:", name.to_uppercase());
+  let width = uses.iter().map(|(zone, _)| zone.name().len()).max();
+  for (zone, offset) in uses {
+    println!(
+      "  {:width$}  {offset}",
+      zone.name(),
+      width = width.unwrap_or_default()
+    );
+  }
+}
 
-  let date = parse_date_args(&args[1..], now);
-  match date {
-    Ok(date) => print!("{}", to_iso(date)),
-    Err(e) => eprintln!("ERROR:\n{e}"),
+fn print_world_clock(date: &ParsedDate, zones: &[Tz], json: bool) {
+  let clock = date.world_clock(zones);
+  if json {
+    let json = serde_json::to_string_pretty(&clock)
+      .unwrap_or_else(|e| exit_with_error(e));
+    println!("{json}");
+  }
+  else {
+    println!("{clock}");
+  }
+}
+
+fn print_overlap(parser: &Parser, zones: &[Tz], hours: WorkingHours, on: &str) {
+  let range = parser
+    .parse_range(on)
+    .unwrap_or_else(|e| exit_with_error(e));
+  let slots = common_working_hours(range, zones, hours)
+    .unwrap_or_else(|e| exit_with_error(e));
+  if slots.is_empty() {
+    exit_with_error(format!("no common working hours in {range}"));
+  }
+  let width = zones.iter().map(|zone| zone.name().len()).max();
+  for (i, slot) in slots.iter().enumerate() {
+    if i > 0 {
+      println!();
+    }
+    let minutes = slot.duration().num_minutes();
+    println!("{slot}  ({}:{:02} h)", minutes / 60, minutes % 60);
+    for zone in zones {
+      println!(
+        "  {:width$}  {}",
+        zone.name(),
+        local_range(slot, *zone),
+        width = width.unwrap_or_default()
+      );
+    }
+  }
+}
+
+// e.g. 'Mon 2024-03-11 09:00 – 12:00'
+fn local_range(range: &DateRange, zone: Tz) -> String {
+  let start = range.start.with_timezone(&zone);
+  let end = range.end.with_timezone(&zone);
+  let end_format = if start.date_naive() == end.date_naive() {
+    "%H:%M"
+  }
+  else {
+    "%a %Y-%m-%d %H:%M"
+  };
+  format!(
+    "{} – {}",
+    start.format("%a %Y-%m-%d %H:%M"),
+    end.format(end_format)
+  )
+}
+
+fn interactive(parser: Parser, pinned: Option<DateTime<Utc>>) {
+  let mut session = Session::new(parser);
+  if let Some(now) = pinned {
+    session = session.now(now);
+  }
+  if let Some(path) = Session::default_history_path() {
+    session = session.history_file(path);
+  }
+  println!("Enter an expression, :help for commands, Ctrl-D to leave");
+  let mut lines = std::io::stdin().lock().lines();
+  loop {
+    print!("tu> ");
+    let _ = std::io::stdout().flush();
+    let Some(Ok(line)) = lines.next() else {
+      println!();
+      break;
+    };
+    match session.eval(&line) {
+      Some(output) if output.is_empty() => (),
+      Some(output) => println!("{output}"),
+      None => break,
+    }
+  }
+}
+
+// the parser configured by the config file and then the flags,
+// and the reference time if it was pinned
+fn parser(
+  settings: &Settings,
+  config: &Config,
+) -> (Parser, Option<DateTime<Utc>>) {
+  let mut parser = config
+    .apply(Parser::new().now(Utc::now()))
+    .unwrap_or_else(|e| exit_with_error(e));
+  if let Some(zone) = settings.zone {
+    parser = parser.zone(zone);
+  }
+  for &zone in &settings.prefer_zones {
+    parser = parser.prefer_zone(zone);
+  }
+  if let Some(dialect) = settings.dialect {
+    parser = parser.dialect(dialect);
+  }
+  if let Some(policy) = settings.direction {
+    parser = parser.direction_policy(policy);
+  }
+  if let Some(policy) = settings.time_policy {
+    parser = parser.time_policy(policy);
+  }
+  if let Some(bias) = settings.prefer {
+    parser = parser.bias(bias);
+  }
+  if let Some(week_start) = settings.week_start {
+    parser = parser.week_start(week_start);
+  }
+  if let Some(lang) = settings.lang {
+    parser = parser.language(lang);
+  }
+  if let Some(unit) = settings.epoch_unit {
+    parser = parser.epoch_unit(unit);
+  }
+  for (name, time) in &settings.times_of_day {
+    parser = parser.time_of_day(name, *time);
+  }
+
+  // TU_NOW and each --now are relative to the reference time before them
+  let now_expressions = std::env::var("TU_NOW")
+    .into_iter()
+    .filter(|value| !value.trim().is_empty())
+    .chain(settings.now.iter().cloned());
+  let mut pinned = None;
+  for expression in now_expressions {
+    let date = parser.parse(&expression).unwrap_or_else(|e| {
+      exit_with_error(format!("reference time '{expression}': {e}"))
+    });
+    parser = parser.now(date.utc);
+    pinned = Some(date.utc);
+  }
+  if pinned.is_some() {
+    parser = config
+      .apply_dates(parser)
+      .unwrap_or_else(|e| exit_with_error(e));
+  }
+  // input formats are for expressions, not for the reference time,
+  // the instants of zone queries or the range of overlap
+  for pattern in &settings.input_formats {
+    parser = parser.input_format(pattern);
+  }
+  // results are rounded, the reference time isn't
+  let rounding = [
+    (Rounding::Round, settings.round),
+    (Rounding::Floor, settings.floor),
+    (Rounding::Ceil, settings.ceil),
+  ]
+  .into_iter()
+  .find_map(|(rounding, unit)| Some((rounding, unit?)));
+  if let Some((rounding, unit)) = rounding {
+    parser = parser.rounding(rounding, unit);
+  }
+  (parser, pinned)
+}
+
+fn resolve(
+  parser: Parser,
+  pinned: Option<DateTime<Utc>>,
+  config: &Config,
+  format: Format,
+  args: ParseArgs,
+) {
+  if args.interactive {
+    return interactive(parser, pinned);
+  }
+  let expression = text(&args.expression);
+
+  if args.all {
+    match parser.interpretations(&expression) {
+      Ok(interpretations) => {
+        for interpretation in interpretations {
+          println!(
+            "{}. {}  ({})",
+            interpretation.rank,
+            interpretation.date.format(format),
+            interpretation.rule
+          );
+        }
+      }
+      Err(e) => exit_with_error(e),
+    }
+    return;
+  }
+
+  if args.explain {
+    match parser.explain(&expression) {
+      Ok(explanation) => println!("{explanation}"),
+      Err(e) => exit_with_error(e),
+    }
+    return;
+  }
+
+  // zones given on the command line replace the configured ones
+  let show_zones = args.show_zones || !args.shown_zones.is_empty();
+  let zones = if args.shown_zones.is_empty() {
+    config.show_zones().unwrap_or_else(|e| exit_with_error(e))
+  }
+  else {
+    args.shown_zones
+  };
+  match parser.parse(&expression) {
+    Ok(date) if show_zones => {
+      if zones.is_empty() {
+        exit_with_error(
+          "no zones to show, set show_zones in the config file \
+           or use --show-zone",
+        );
+      }
+      if !args.json {
+        println!("{}", date.format(format));
+      }
+      print_world_clock(&date, &zones, args.json);
+    }
+    Ok(date) => print!("{}", date.format(format)),
+    Err(e) => exit_with_error(e),
+  }
+}
+
+// completions suggest the words of `lang` for expressions
+fn completions(shell: Shell, lang: &'static dyn Language) {
+  fn with_vocabulary(
+    command: clap::Command,
+    lang: &'static dyn Language,
+  ) -> clap::Command {
+    let command = command.mut_args(|arg| {
+      if arg.get_value_parser().possible_values().is_some()
+        && arg.is_hide_possible_values_set()
+      {
+        arg.value_parser(Vocabulary(lang))
+      }
+      else {
+        arg
+      }
+    });
+    command.mut_subcommands(|sub| with_vocabulary(sub, lang))
+  }
+  let mut command = with_vocabulary(Cli::command(), lang);
+  clap_complete::generate(shell, &mut command, "tu", &mut std::io::stdout());
+  // fish completions only list values of options, not of expressions
+  if shell == Shell::Fish {
+    println!(
+      "complete -c tu -n \"not __fish_seen_subcommand_from \
+       zones zone overlap convert completions help\" -f -a \"{}\"",
+      lang.vocabulary().join(" ")
+    );
+  }
+}
+
+fn main() {
+  let cli = Cli::parse();
+  let config_path = cli
+    .settings
+    .config
+    .clone()
+    .or_else(|| Config::default_path().filter(|path| path.exists()));
+  let config = match config_path {
+    Some(path) => Config::load(&path).unwrap_or_else(|e| exit_with_error(e)),
+    None => Config::default(),
+  };
+  let (parser, pinned) = parser(&cli.settings, &config);
+  let format = cli
+    .settings
+    .format
+    .or_else(|| config.format().unwrap_or_else(|e| exit_with_error(e)));
+
+  let Some(command) = cli.command else {
+    return resolve(
+      parser,
+      pinned,
+      &config,
+      format.unwrap_or_default(),
+      cli.parse,
+    );
+  };
+  match command {
+    Command::Parse(args) => {
+      resolve(parser, pinned, &config, format.unwrap_or_default(), args)
+    }
+    Command::Duration(expression) => {
+      let duration = parser
+        .duration(&expression.text())
+        .unwrap_or_else(|e| exit_with_error(e));
+      println!("{}", iso_duration(duration));
+    }
+    Command::Diff { from, to } => {
+      let duration = parser
+        .diff(&from, &to)
+        .unwrap_or_else(|e| exit_with_error(e));
+      println!("{}", iso_duration(duration));
+    }
+    Command::Range(expression) => {
+      let range = parser
+        .parse_range(&expression.text())
+        .unwrap_or_else(|e| exit_with_error(e));
+      println!("{range}");
+    }
+    Command::Humanize(expression) => {
+      let duration = parser
+        .duration(&expression.text())
+        .unwrap_or_else(|e| exit_with_error(e));
+      println!("{}", humanize(duration));
+    }
+    Command::Zones { filter, at } => print_zones(
+      &parser.without_input_formats(),
+      &filter.join(" "),
+      at.as_deref(),
+    ),
+    Command::Zone { name, at } => {
+      print_zone(&parser.without_input_formats(), &name, at.as_deref())
+    }
+    Command::Overlap { zones, hours, on } => {
+      print_overlap(&parser.without_input_formats(), &zones, hours, &on)
+    }
+    Command::Convert { to, expression } => {
+      let (to, expression) = convert_args(to, &expression.expression);
+      let date = parser
+        .parse(&expression)
+        .unwrap_or_else(|e| exit_with_error(e));
+      println!(
+        "{}",
+        date.in_zone(to).format(format.unwrap_or(Format::Local))
+      );
+    }
+    Command::Completions { shell } => {
+      completions(shell, cli.settings.lang.unwrap_or(&ENGLISH))
+    }
   }
 }
@@ -0,0 +1,518 @@
+use std::collections::BTreeMap;
+
+use chrono::prelude::{DateTime, Datelike, NaiveTime, Utc};
+use chrono::Months;
+use chrono_tz::Tz;
+use serde::Serialize;
+
+use crate::chrono_english::language::Language;
+use crate::chrono_english::lib::{
+  date_error, parse_spec, resolve, Ambiguity, Bias, DateError, Dialect,
+  DirectionPolicy, Options, TimePolicy, WeekStart,
+};
+use crate::chrono_english::types::{
+  from_local, ByName, DateSpec, DateTimeSpec, Interval, PeriodSpec, Skip, Trace,
+};
+use crate::epoch::{parse_epoch, parse_tagged, EpochUnit};
+use crate::explain::Explanation;
+use crate::layout::parse_layout;
+use crate::pattern::parse_pattern;
+use crate::range::DateRange;
+use crate::round::{round, Rounding, TimeUnit};
+use crate::to_iso;
+
+/// The rule which recognized an input
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Serialize)]
+pub enum InputKind {
+  /// Seconds, milliseconds, microseconds or nanoseconds
+  /// since 1970-01-01T00:00:00Z
+  UnixTimestamp,
+  /// A count since another epoch, tagged with its time scale,
+  /// e.g. `excel:45678.5` or `gps:1400000000`
+  Serial,
+  /// E.g. `Wed, 14 Feb 2024 23:16:09 GMT`
+  Rfc2822,
+  /// E.g. `2024-04-10T13:31:46+04:00`
+  Rfc3339,
+  /// A timestamp as printed by logs and programming languages,
+  /// e.g. `10/Oct/2000:13:55:36 -0700` or `Wed Jun 30 21:49:08 1993`
+  Layout,
+  /// Matched a pattern given with [`Parser::input_format`]
+  Pattern,
+  /// A natural language expression like `next friday 4pm`
+  Natural,
+  /// A date defined with [`Parser::named_date`]
+  NamedDate,
+}
+
+/// The result of a successful parse
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParsedDate {
+  /// The resolved instant
+  pub utc: DateTime<Utc>,
+  /// The resolved instant in the zone of the parser
+  pub local: DateTime<Tz>,
+  /// The rule which recognized the input
+  pub kind: InputKind,
+}
+
+impl ParsedDate {
+  pub(crate) fn new(utc: DateTime<Utc>, zone: Tz, kind: InputKind) -> Self {
+    ParsedDate {
+      utc,
+      local: utc.with_timezone(&zone),
+      kind,
+    }
+  }
+
+  /// Format the instant as RFC 3339 with a `Z` suffix
+  pub fn to_iso(&self) -> String {
+    to_iso(self.utc)
+  }
+
+  /// The same instant in another zone
+  pub fn in_zone(&self, zone: Tz) -> ParsedDate {
+    ParsedDate::new(self.utc, zone, self.kind)
+  }
+}
+
+/// Intermediate results of a parse, kept for explanations
+struct Resolution {
+  parsed: ParsedDate,
+  normalized: String,
+  spec: Option<DateTimeSpec>,
+  trace: Trace,
+}
+
+/// Configurable parser for natural language date/time expressions.
+///
+/// ```
+/// use chrono::{TimeZone, Utc};
+/// use tu::{Dialect, Parser, Tz};
+///
+/// let parser = Parser::new()
+///   .now(Utc.with_ymd_and_hms(2024, 1, 1, 9, 0, 0).unwrap())
+///   .zone(Tz::Europe__Berlin)
+///   .dialect(Dialect::Uk);
+///
+/// let date = parser.parse("tomorrow 4pm").unwrap();
+/// assert_eq!(date.to_iso(), "2024-01-02T15:00:00Z");
+/// ```
+#[derive(Debug, Clone)]
+pub struct Parser {
+  now: Option<DateTime<Utc>>,
+  pub(crate) zone: Tz,
+  pub(crate) options: Options,
+  aliases: BTreeMap<String, String>,
+  named_dates: BTreeMap<String, DateTime<Utc>>,
+  epoch_unit: Option<EpochUnit>,
+  input_formats: Vec<String>,
+  rounding: Option<(Rounding, TimeUnit)>,
+}
+
+impl Default for Parser {
+  fn default() -> Parser {
+    Parser::new()
+  }
+}
+
+impl Parser {
+  /// A parser using the current time, UTC and US dialect
+  pub fn new() -> Parser {
+    Parser {
+      now: None,
+      zone: Tz::UTC,
+      options: Options::default(),
+      aliases: BTreeMap::new(),
+      named_dates: BTreeMap::new(),
+      epoch_unit: None,
+      input_formats: Vec::new(),
+      rounding: None,
+    }
+  }
+
+  /// Reference time for relative expressions (default: current time)
+  pub fn now(mut self, now: DateTime<Utc>) -> Parser {
+    self.now = Some(now);
+    self
+  }
+
+  /// Zone in which expressions without an explicit offset are interpreted
+  pub fn zone(mut self, zone: Tz) -> Parser {
+    self.zone = zone;
+    self.options.zone = Some(zone);
+    self
+  }
+
+  /// Zone which decides what an ambiguous abbreviation like `IST` means,
+  /// unless the zone of the parser uses it.
+  /// Zones added first take precedence.
+  pub fn prefer_zone(mut self, zone: Tz) -> Parser {
+    self.options.preferred_zones.push(zone);
+    self
+  }
+
+  /// Day/month order of numeric dates like `9/11`
+  pub fn dialect(mut self, dialect: Dialect) -> Parser {
+    self.options.dialect = Some(dialect);
+    self
+  }
+
+  /// Language of the expressions (default: English).
+  /// Languages which write dates day first also switch
+  /// numeric dates to day/month order unless a dialect is set.
+  pub fn language(mut self, language: &'static dyn Language) -> Parser {
+    self.options.language = language;
+    self
+  }
+
+  /// Meaning of "next", "this" and "last" before weekdays and months
+  /// (default: `Closest` for US and `Period` for UK English)
+  pub fn direction_policy(mut self, policy: DirectionPolicy) -> Parser {
+    self.options.direction = Some(policy);
+    self
+  }
+
+  /// Time of day of dates without a time, like "tomorrow" or "friday"
+  /// (default: `Preserve` for relative and `StartOfDay` for other dates)
+  pub fn time_policy(mut self, policy: TimePolicy) -> Parser {
+    self.options.time_policy = Some(policy);
+    self
+  }
+
+  /// Which occurrence "friday", "march", "5 may" or a date without a year
+  /// stands for (default: the next weekday, or in the current year)
+  pub fn bias(mut self, bias: Bias) -> Parser {
+    self.options.bias = Some(bias);
+    self
+  }
+
+  /// First day of the week
+  pub fn week_start(mut self, week_start: WeekStart) -> Parser {
+    self.options.week_start = week_start;
+    self
+  }
+
+  /// Add a named time of day or change the time of a predefined one
+  /// (midnight, morning, noon, midday, afternoon, eod, eob, cob, evening, night)
+  pub fn time_of_day(mut self, name: &str, time: NaiveTime) -> Parser {
+    self.options.times_of_day.set(name, time);
+    self
+  }
+
+  /// Resolution of local times skipped or repeated by DST transitions
+  pub fn ambiguity(mut self, ambiguity: Ambiguity) -> Parser {
+    self.options.ambiguity = ambiguity;
+    self
+  }
+
+  /// Fail on unrecognized trailing text instead of ignoring it
+  pub fn strict(mut self, strict: bool) -> Parser {
+    self.options.strict = strict;
+    self
+  }
+
+  /// Expand the input `name` to `expression` before parsing it,
+  /// like "sprint end" to "next friday 18:00"
+  pub fn alias(mut self, name: &str, expression: &str) -> Parser {
+    self.aliases.insert(key(name), expression.to_string());
+    self
+  }
+
+  /// Resolve the input `name` to a fixed instant
+  pub fn named_date(mut self, name: &str, date: DateTime<Utc>) -> Parser {
+    self.named_dates.insert(key(name), date);
+    self
+  }
+
+  /// Unit of numeric timestamps
+  /// (default: guessed from the number of digits)
+  pub fn epoch_unit(mut self, unit: EpochUnit) -> Parser {
+    self.epoch_unit = Some(unit);
+    self
+  }
+
+  /// Accept inputs matching a `strptime`-style pattern like `%d.%m.%Y %H%M`.
+  /// Several patterns are tried in the order they were added,
+  /// and replace the built-in formats and natural language.
+  pub fn input_format(mut self, pattern: impl Into<String>) -> Parser {
+    self.input_formats.push(pattern.into());
+    self
+  }
+
+  /// The same parser without the patterns of [`Parser::input_format`],
+  /// e.g. for reference times
+  pub fn without_input_formats(mut self) -> Parser {
+    self.input_formats.clear();
+    self
+  }
+
+  /// Round results to a unit in the zone of the parser,
+  /// e.g. `Rounding::Floor` and `TimeUnit::Day` for midnight
+  pub fn rounding(mut self, rounding: Rounding, unit: TimeUnit) -> Parser {
+    self.rounding = Some((rounding, unit));
+    self
+  }
+
+  /// The reference time used for relative expressions
+  pub fn reference_time(&self) -> DateTime<Utc> {
+    self.now.unwrap_or_else(Utc::now)
+  }
+
+  /// Parse command line arguments, which are joined with spaces
+  pub fn parse_args(&self, args: &[String]) -> Result<ParsedDate, DateError> {
+    self.parse(&args.join(" "))
+  }
+
+  /// Parse a date/time expression
+  pub fn parse(&self, input: &str) -> Result<ParsedDate, DateError> {
+    self.resolve(input).map(|resolution| resolution.parsed)
+  }
+
+  /// Parse a date/time expression and report how it was resolved
+  pub fn explain(&self, input: &str) -> Result<Explanation, DateError> {
+    let resolution = self.resolve(input)?;
+    Ok(Explanation {
+      input: input.to_string(),
+      normalized: resolution.normalized,
+      kind: resolution.parsed.kind,
+      spec: resolution.spec,
+      steps: resolution.trace.steps,
+      utc: resolution.parsed.utc,
+    })
+  }
+
+  /// Parse an expression for a period like `next week`, `march`
+  /// or `tomorrow`, which covers the whole week, month, year or day
+  /// in the zone of the parser
+  pub fn parse_range(&self, input: &str) -> Result<DateRange, DateError> {
+    self.period(&self.resolve_instant(input)?, input)
+  }
+
+  fn period(
+    &self,
+    resolution: &Resolution,
+    input: &str,
+  ) -> Result<DateRange, DateError> {
+    let not_a_period = || {
+      date_error(format!(
+        "'{input}' is a point in time, not a day, week, month or year"
+      ))
+    };
+    let date = resolution
+      .spec
+      .as_ref()
+      .filter(|spec| spec.time.is_none())
+      .and_then(|spec| spec.date.as_ref())
+      .ok_or_else(not_a_period)?;
+    let day = resolution.parsed.local.date_naive();
+    let (first, end) = match date {
+      DateSpec::Week(_) => (day, day + chrono::Duration::days(7)),
+      DateSpec::Period(PeriodSpec {
+        unit: Interval::Months(months),
+        ..
+      }) => {
+        let end = day.checked_add_months(Months::new(*months as u32));
+        (day, end.ok_or_else(not_a_period)?)
+      }
+      DateSpec::Period(PeriodSpec {
+        unit: Interval::Seconds(_),
+        ..
+      }) => return Err(not_a_period()),
+      DateSpec::FromName(ByName::MonthName(_)) => {
+        let first = day.with_day(1).ok_or_else(not_a_period)?;
+        let end = first.checked_add_months(Months::new(1));
+        (first, end.ok_or_else(not_a_period)?)
+      }
+      DateSpec::Relative(Skip {
+        unit: Interval::Seconds(_),
+        ..
+      }) => return Err(not_a_period()),
+      _ => (day, day.succ_opt().ok_or_else(not_a_period)?),
+    };
+    let midnight = |date: chrono::NaiveDate| {
+      from_local(
+        &self.zone,
+        &date.and_time(NaiveTime::MIN),
+        Ambiguity::Earliest,
+      )
+      .map(|local| local.with_timezone(&Utc))
+      .ok_or_else(not_a_period)
+    };
+    Ok(DateRange::new(midnight(first)?, midnight(end)?))
+  }
+
+  fn resolve(&self, input: &str) -> Result<Resolution, DateError> {
+    let mut resolution = self.resolve_instant(input)?;
+    if let Some((rounding, unit)) = self.rounding {
+      let local = &resolution.parsed.local;
+      let rounded = round(local, rounding, unit, self.options.week_start)
+        .ok_or_else(|| {
+          date_error(format!("cannot round to the {}", unit.name()))
+        })?;
+      resolution.trace.push(
+        format!("{} to the {}", rounding.name(), unit.name()),
+        &rounded,
+      );
+      resolution.parsed = ParsedDate::new(
+        rounded.with_timezone(&Utc),
+        self.zone,
+        resolution.parsed.kind,
+      );
+    }
+    Ok(resolution)
+  }
+
+  fn resolve_instant(&self, input: &str) -> Result<Resolution, DateError> {
+    if !self.input_formats.is_empty() {
+      return self.resolve_pattern(&self.expand_aliases(input));
+    }
+    let now = self.reference_time();
+    let normalized = normalize(&self.expand_aliases(input));
+    let mut trace = Trace::default();
+    let mut spec = None;
+
+    let (utc, kind) =
+      if let Some(datetime) = self.named_dates.get(&key(&normalized)) {
+        trace.push(format!("named date '{}'", key(&normalized)), datetime);
+        (*datetime, InputKind::NamedDate)
+      }
+      else if let Some(datetime) =
+        parse_epoch(&normalized, self.epoch_unit, &mut trace)
+      {
+        (datetime, InputKind::UnixTimestamp)
+      }
+      else if let Some(datetime) = parse_tagged(&normalized, &mut trace) {
+        (datetime?, InputKind::Serial)
+      }
+      else if let Ok(datetime) = DateTime::parse_from_rfc2822(&normalized) {
+        trace.push("parsed as RFC 2822", &datetime);
+        (datetime.with_timezone(&Utc), InputKind::Rfc2822)
+      }
+      else if let Ok(datetime) = DateTime::parse_from_rfc3339(&normalized) {
+        trace.push("parsed as RFC 3339", &datetime);
+        (datetime.with_timezone(&Utc), InputKind::Rfc3339)
+      }
+      else if let Some(datetime) = parse_layout(
+        &normalized,
+        now.with_timezone(&self.zone),
+        self.options.ambiguity,
+        self.options.bias,
+        &mut trace,
+      ) {
+        (datetime, InputKind::Layout)
+      }
+      else {
+        let local_now = now.with_timezone(&self.zone);
+        let parsed = parse_spec(&normalized, &self.options)?;
+        let datetime = resolve(&parsed, local_now, &self.options, &mut trace)?;
+        spec = Some(parsed);
+        (datetime.with_timezone(&Utc), InputKind::Natural)
+      };
+    trace.push("converted to UTC", &utc);
+
+    Ok(Resolution {
+      parsed: ParsedDate::new(utc, self.zone, kind),
+      normalized,
+      spec,
+      trace,
+    })
+  }
+
+  // the first matching pattern wins, otherwise all mismatches are reported
+  fn resolve_pattern(&self, input: &str) -> Result<Resolution, DateError> {
+    let input = input.trim();
+    let now = self.reference_time().with_timezone(&self.zone);
+    let mut mismatches = Vec::new();
+    for pattern in &self.input_formats {
+      match parse_pattern(
+        input,
+        pattern,
+        now,
+        self.options.ambiguity,
+        self.options.bias,
+      ) {
+        Ok(utc) => {
+          let mut trace = Trace::default();
+          trace.push(format!("matched input format '{pattern}'"), &utc);
+          trace.push("converted to UTC", &utc);
+          return Ok(Resolution {
+            parsed: ParsedDate::new(utc, self.zone, InputKind::Pattern),
+            normalized: input.to_string(),
+            spec: None,
+            trace,
+          });
+        }
+        Err(e) => mismatches.push(e.to_string()),
+      }
+    }
+    Err(date_error(format!(
+      "'{input}' matches no input format:\n{}",
+      mismatches.join("\n")
+    )))
+  }
+
+  // aliases may expand to other aliases, but not in a cycle
+  fn expand_aliases(&self, input: &str) -> String {
+    let mut expanded = input.to_string();
+    for _ in 0..=self.aliases.len() {
+      match self.aliases.get(&key(&expanded)) {
+        Some(expression) => expanded.clone_from(expression),
+        None => break,
+      }
+    }
+    expanded
+  }
+}
+
+/// Aliases and named dates are matched case-insensitively
+fn key(name: &str) -> String {
+  name
+    .split_whitespace()
+    .collect::<Vec<_>>()
+    .join(" ")
+    .to_lowercase()
+}
+
+// TODO: Remove after https://github.com/chronotope/chrono/issues/1228
+fn append_min_if_only_hour(input: &str) -> String {
+  if let Some(last_three) = input.get(input.len().saturating_sub(3)..) {
+    // Check if first char is '+' and then two digits
+    if last_three.starts_with('+')
+      && last_three.chars().nth(1).map(|ch| ch.is_ascii_digit()) == Some(true)
+      && last_three.chars().nth(2).map(|ch| ch.is_ascii_digit()) == Some(true)
+    {
+      return format!("{input}:00");
+    }
+  }
+
+  if let Some(last_two_char) = input.get(input.len().saturating_sub(2)..) {
+    if last_two_char.starts_with('+') {
+      let last_char_opt = last_two_char.chars().nth(1);
+      if let Some(last_char) = last_char_opt {
+        if last_char.is_ascii_digit() {
+          let without_last_two = &input[..input.len() - 2];
+          return format!("{without_last_two}+0{last_char}:00");
+        }
+      }
+    }
+  }
+
+  input.to_string()
+}
+
+/// Remove "in" from the beginning
+fn normalize(input: &str) -> String {
+  append_min_if_only_hour(
+    {
+      if let Some(stripped) = input.strip_prefix("in ") {
+        stripped.to_string()
+      }
+      else {
+        input.to_string()
+      }
+    }
+    .trim(),
+  )
+}
@@ -0,0 +1,221 @@
+use chrono::format::{
+  parse_and_remainder, Item, ParseErrorKind, Parsed, StrftimeItems,
+};
+use chrono::prelude::{DateTime, Datelike, FixedOffset, TimeZone, Utc};
+use chrono_tz::Tz;
+
+use crate::chrono_english::lib::{
+  date_error, Ambiguity, Bias, DateError, DateResult,
+};
+use crate::chrono_english::types::from_local;
+
+/// Parse `input` with a `strptime`-style pattern like `%d.%m.%Y %H%M`.
+/// Without any date the time is on today, otherwise a missing year
+/// is the current one (or the one `bias` prefers)
+/// and a missing month or day the first one.
+/// A missing time is midnight.
+/// Without an offset in the input the zone of `now` applies.
+pub(crate) fn parse_pattern(
+  input: &str,
+  pattern: &str,
+  now: DateTime<Tz>,
+  ambiguity: Ambiguity,
+  bias: Option<Bias>,
+) -> DateResult<DateTime<Utc>> {
+  let mismatch = |detail: String| {
+    date_error(format!("'{pattern}' does not match: {detail}"))
+  };
+
+  let mut parsed = Parsed::new();
+  let mut rest = input;
+  for token in tokens(pattern) {
+    let items: Vec<Item> = StrftimeItems::new(token).collect();
+    if items.iter().any(|item| matches!(item, Item::Error)) {
+      return Err(date_error(format!(
+        "invalid pattern '{pattern}': unknown specifier '{token}'"
+      )));
+    }
+    let column = input[..input.len() - rest.len()].chars().count() + 1;
+    rest = parse_and_remainder(&mut parsed, rest, items.into_iter()).map_err(
+      |e| {
+        let field = describe(token);
+        mismatch(match e.kind() {
+          ParseErrorKind::OutOfRange => {
+            format!("{field} out of range at column {column}")
+          }
+          ParseErrorKind::Impossible => {
+            format!("{field} at column {column} contradicts an earlier field")
+          }
+          _ if rest.is_empty() => {
+            format!("expected {field} at column {column}, found end of input")
+          }
+          _ => format!("expected {field} at column {column}, found '{rest}'"),
+        })
+      },
+    )?;
+  }
+  if !rest.is_empty() {
+    let column = input[..input.len() - rest.len()].chars().count() + 1;
+    return Err(mismatch(format!(
+      "unexpected '{rest}' at column {column} after the end of the pattern"
+    )));
+  }
+
+  if let Some(timestamp) = parsed.timestamp() {
+    return DateTime::from_timestamp(
+      timestamp,
+      parsed.nanosecond().unwrap_or(0),
+    )
+    .ok_or_else(|| mismatch("timestamp out of range".to_string()));
+  }
+  let invalid = |_| mismatch("not a valid date and time".to_string());
+  let by_week = parsed.isoweek().is_some()
+    || parsed.week_from_mon().is_some()
+    || parsed.week_from_sun().is_some();
+  let has_date = parsed.year().is_some()
+    || parsed.year_mod_100().is_some()
+    || parsed.isoyear().is_some()
+    || parsed.month().is_some()
+    || parsed.day().is_some()
+    || parsed.ordinal().is_some()
+    || by_week;
+  if !has_date {
+    // a time of today
+    parsed.set_month(now.month().into()).map_err(invalid)?;
+    parsed.set_day(now.day().into()).map_err(invalid)?;
+  }
+  let without_year = parsed.year().is_none()
+    && parsed.year_mod_100().is_none()
+    && parsed.isoyear().is_none();
+  // the bias picks the year later
+  let bias = bias.filter(|_| has_date && without_year);
+  if without_year && bias.is_none() {
+    parsed.set_year(now.year().into()).map_err(invalid)?;
+  }
+  if parsed.ordinal().is_none() && !by_week {
+    if parsed.month().is_none() {
+      parsed.set_month(1).map_err(invalid)?;
+    }
+    if parsed.day().is_none() {
+      parsed.set_day(1).map_err(invalid)?;
+    }
+  }
+  let without_time = parsed.hour_mod_12().is_none();
+  if without_time {
+    parsed.set_hour(0).map_err(invalid)?;
+  }
+  if parsed.minute().is_none() {
+    parsed.set_minute(0).map_err(invalid)?;
+  }
+  if parsed.hour_div_12().is_none() {
+    return Err(mismatch("a 12-hour clock needs %p for AM/PM".to_string()));
+  }
+
+  let instant = |parsed: &Parsed| resolve(parsed, now, ambiguity, &mismatch);
+  let Some(bias) = bias else {
+    return instant(&parsed);
+  };
+  // a whole day without a time, otherwise an instant,
+  // in enough years to find a Feb 29
+  let mut periods = Vec::new();
+  for year in now.year() - 4..=now.year() + 4 {
+    let mut parsed = parsed.clone();
+    parsed.set_year(year.into()).map_err(invalid)?;
+    if let Ok(start) = instant(&parsed) {
+      let end = if without_time {
+        start + chrono::Duration::days(1)
+      }
+      else {
+        start
+      };
+      periods.push((start, end));
+    }
+  }
+  match bias.choose(&periods, &now.with_timezone(&Utc)) {
+    Some(i) => Ok(periods[i].0),
+    None => {
+      parsed.set_year(now.year().into()).map_err(invalid)?;
+      instant(&parsed)
+    }
+  }
+}
+
+fn resolve(
+  parsed: &Parsed,
+  now: DateTime<Tz>,
+  ambiguity: Ambiguity,
+  mismatch: &dyn Fn(String) -> DateError,
+) -> DateResult<DateTime<Utc>> {
+  let date = parsed
+    .to_naive_date()
+    .map_err(|_| mismatch("no such date".to_string()))?;
+  let time = parsed
+    .to_naive_time()
+    .map_err(|_| mismatch("no such time".to_string()))?;
+  let naive = date.and_time(time);
+  let datetime = match parsed.offset() {
+    Some(offset) => FixedOffset::east_opt(offset)
+      .and_then(|offset| offset.from_local_datetime(&naive).single())
+      .map(|datetime| datetime.with_timezone(&Utc)),
+    None => from_local(&now.timezone(), &naive, ambiguity)
+      .map(|datetime| datetime.with_timezone(&Utc)),
+  };
+  datetime.ok_or_else(|| mismatch("not a valid date and time".to_string()))
+}
+
+/// Split a pattern into specifiers like `%d`, `%-d` or `%.3f`
+/// and the literal text between them
+fn tokens(pattern: &str) -> Vec<&str> {
+  let mut tokens = Vec::new();
+  let mut start = 0;
+  let mut chars = pattern.char_indices();
+  while let Some((i, c)) = chars.next() {
+    if c != '%' {
+      continue;
+    }
+    if start < i {
+      tokens.push(&pattern[start..i]);
+    }
+    // flags and widths up to the conversion character
+    let mut end = pattern.len();
+    for (j, c) in chars.by_ref() {
+      if !matches!(c, '-' | '_' | '0'..='9' | '.' | ':' | '#') {
+        end = j + c.len_utf8();
+        break;
+      }
+    }
+    tokens.push(&pattern[i..end]);
+    start = end;
+  }
+  if start < pattern.len() {
+    tokens.push(&pattern[start..]);
+  }
+  tokens
+}
+
+// e.g. "%m (month)" or "'.'"
+fn describe(token: &str) -> String {
+  if !token.starts_with('%') {
+    return format!("'{token}'");
+  }
+  let field = match token.chars().last() {
+    Some('Y') => "year",
+    Some('y') => "two-digit year",
+    Some('m') => "month",
+    Some('b' | 'h' | 'B') => "month name",
+    Some('d' | 'e') => "day",
+    Some('j') => "day of the year",
+    Some('a' | 'A') => "weekday name",
+    Some('H' | 'k') => "hour",
+    Some('I' | 'l') => "12-hour clock hour",
+    Some('M') => "minute",
+    Some('S') => "second",
+    Some('f') => "fraction of a second",
+    Some('p' | 'P') => "AM/PM",
+    Some('z') => "offset",
+    Some('Z') => "zone name",
+    Some('s') => "Unix timestamp",
+    _ => return format!("'{token}'"),
+  };
+  format!("{token} ({field})")
+}
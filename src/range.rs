@@ -0,0 +1,140 @@
+use std::fmt;
+
+use chrono::prelude::{DateTime, Datelike, NaiveTime, Utc, Weekday};
+use chrono::Duration;
+use chrono_tz::Tz;
+use serde::Serialize;
+
+use crate::chrono_english::lib::{
+  date_error, date_result, Ambiguity, DateError, DateResult,
+};
+use crate::chrono_english::types::from_local;
+use crate::to_iso;
+
+/// A period from `start` up to, but not including, `end`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct DateRange {
+  pub start: DateTime<Utc>,
+  pub end: DateTime<Utc>,
+}
+
+impl DateRange {
+  pub fn new(start: DateTime<Utc>, end: DateTime<Utc>) -> DateRange {
+    DateRange { start, end }
+  }
+
+  pub fn duration(&self) -> Duration {
+    self.end - self.start
+  }
+
+  /// The period both ranges cover, if any
+  pub fn intersect(&self, other: &DateRange) -> Option<DateRange> {
+    let start = self.start.max(other.start);
+    let end = self.end.min(other.end);
+    (start < end).then_some(DateRange { start, end })
+  }
+}
+
+/// An ISO 8601 interval like `2024-03-11T13:00:00Z/2024-03-11T16:00:00Z`
+impl fmt::Display for DateRange {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(f, "{}/{}", to_iso(self.start), to_iso(self.end))
+  }
+}
+
+/// Daily working hours like `9-17` or `08:30-16:30`,
+/// which apply from Monday to Friday
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WorkingHours {
+  pub start: NaiveTime,
+  /// Before `start` for hours past midnight, like `22-6`
+  pub end: NaiveTime,
+}
+
+impl Default for WorkingHours {
+  fn default() -> WorkingHours {
+    WorkingHours {
+      start: NaiveTime::from_hms_opt(9, 0, 0).unwrap(),
+      end: NaiveTime::from_hms_opt(17, 0, 0).unwrap(),
+    }
+  }
+}
+
+impl std::str::FromStr for WorkingHours {
+  type Err = DateError;
+
+  fn from_str(s: &str) -> DateResult<WorkingHours> {
+    let time = |s: &str| {
+      let s = s.trim();
+      NaiveTime::parse_from_str(s, "%H:%M").ok().or_else(|| {
+        s.parse()
+          .ok()
+          .and_then(|h| NaiveTime::from_hms_opt(h, 0, 0))
+      })
+    };
+    let hours = s.split_once('-').and_then(|(start, end)| {
+      Some(WorkingHours {
+        start: time(start)?,
+        end: time(end)?,
+      })
+    });
+    match hours {
+      Some(hours) if hours.start != hours.end => Ok(hours),
+      _ => date_result("expected working hours like 9-17 or 08:30-16:30"),
+    }
+  }
+}
+
+/// The slots within `range` which are inside the working hours
+/// of every zone, in order
+pub fn common_working_hours(
+  range: DateRange,
+  zones: &[Tz],
+  hours: WorkingHours,
+) -> DateResult<Vec<DateRange>> {
+  let Some((first, others)) = zones.split_first() else {
+    return Err(date_error("no zones to find common working hours of"));
+  };
+  let mut slots = working_hours(*first, range, hours);
+  for &zone in others {
+    let theirs = working_hours(zone, range, hours);
+    slots = slots
+      .iter()
+      .flat_map(|slot| theirs.iter().filter_map(|other| slot.intersect(other)))
+      .collect();
+  }
+  Ok(slots)
+}
+
+// working hours of the days of `zone` which touch `range`
+fn working_hours(
+  zone: Tz,
+  range: DateRange,
+  hours: WorkingHours,
+) -> Vec<DateRange> {
+  let instant = |date: chrono::NaiveDate, time| {
+    from_local(&zone, &date.and_time(time), Ambiguity::Earliest)
+      .map(|local| local.with_timezone(&Utc))
+  };
+  // hours past midnight start the day before
+  let first = range.start.with_timezone(&zone).date_naive() - Duration::days(1);
+  let last = range.end.with_timezone(&zone).date_naive();
+  first
+    .iter_days()
+    .take_while(|date| *date <= last)
+    .filter(|date| !matches!(date.weekday(), Weekday::Sat | Weekday::Sun))
+    .filter_map(|date| {
+      let end_date = if hours.end <= hours.start {
+        date.succ_opt()?
+      }
+      else {
+        date
+      };
+      let day = DateRange::new(
+        instant(date, hours.start)?,
+        instant(end_date, hours.end)?,
+      );
+      day.intersect(&range)
+    })
+    .collect()
+}
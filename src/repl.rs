@@ -0,0 +1,229 @@
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::PathBuf;
+
+use chrono::prelude::{DateTime, Utc};
+use chrono_tz::Tz;
+
+use crate::parser::Parser;
+use crate::to_iso;
+use crate::zones::ZoneInfo;
+
+/// Number of lines kept in the history file
+const HISTORY_SIZE: usize = 1000;
+
+const HELP: &str = "\
+Enter an expression to resolve it, or one of:
+  :now <expression>  Pin the reference time, :now alone follows the clock
+  :zone <IANA name>  Zone of the expressions
+  :local             Show the result in the zone as well (toggle)
+  :explain           Show how the result was resolved (toggle)
+  :history           List earlier lines, !! repeats the last one, !<n> line n
+  :quit              Leave (as does Ctrl-D)";
+
+/// An interactive session, which resolves one line at a time
+/// and keeps a history across sessions
+#[derive(Debug, Clone)]
+pub struct Session {
+  parser: Parser,
+  /// Reference time pinned with `:now`, otherwise the clock
+  pinned: Option<DateTime<Utc>>,
+  local: bool,
+  explain: bool,
+  history: Vec<String>,
+  history_path: Option<PathBuf>,
+}
+
+impl Session {
+  /// A session with the settings of `parser`,
+  /// showing local times if its zone isn't UTC
+  pub fn new(parser: Parser) -> Session {
+    Session {
+      local: parser.zone != Tz::UTC,
+      parser,
+      pinned: None,
+      explain: true,
+      history: Vec::new(),
+      history_path: None,
+    }
+  }
+
+  /// Pin the reference time, as `:now` does
+  pub fn now(mut self, now: DateTime<Utc>) -> Session {
+    self.pinned = Some(now);
+    self
+  }
+
+  /// `$XDG_DATA_HOME/tu/history` or `~/.local/share/tu/history`
+  pub fn default_history_path() -> Option<PathBuf> {
+    let data_dir = std::env::var_os("XDG_DATA_HOME")
+      .filter(|dir| !dir.is_empty())
+      .map(PathBuf::from)
+      .or_else(|| {
+        std::env::var_os("HOME")
+          .map(|home| PathBuf::from(home).join(".local").join("share"))
+      })?;
+    Some(data_dir.join("tu").join("history"))
+  }
+
+  /// Continue the history stored in `path`, and add new lines to it
+  pub fn history_file(mut self, path: PathBuf) -> Session {
+    if let Ok(text) = fs::read_to_string(&path) {
+      self.history = text.lines().map(str::to_string).collect();
+      let excess = self.history.len().saturating_sub(HISTORY_SIZE);
+      self.history.drain(..excess);
+    }
+    self.history_path = Some(path);
+    self
+  }
+
+  /// Lines entered so far, the oldest first
+  pub fn history(&self) -> &[String] {
+    &self.history
+  }
+
+  /// Resolve a line or run a command, and return what to print,
+  /// or `None` when the session ends
+  pub fn eval(&mut self, line: &str) -> Option<String> {
+    let line = line.trim();
+    if line.is_empty() {
+      return Some(String::new());
+    }
+    if matches!(line, ":q" | ":quit" | ":exit") {
+      return None;
+    }
+    let (line, echo) = match self.recall(line) {
+      Ok(Some(recalled)) => (recalled, true),
+      Ok(None) => (line.to_string(), false),
+      Err(e) => return Some(e),
+    };
+    self.remember(&line);
+
+    let output = self.run(&line);
+    Some(if echo {
+      format!("{line}\n{output}")
+    }
+    else {
+      output
+    })
+  }
+
+  fn run(&mut self, line: &str) -> String {
+    let (command, argument) = match line.split_once(' ') {
+      Some((command, argument)) => (command, argument.trim()),
+      None => (line, ""),
+    };
+    match command {
+      ":help" | ":h" => HELP.to_string(),
+      ":now" if argument.is_empty() => {
+        self.pinned = None;
+        "reference time: the clock".to_string()
+      }
+      ":now" => match self.parser().without_input_formats().parse(argument) {
+        Ok(date) => {
+          self.pinned = Some(date.utc);
+          format!("reference time: {}", date.to_iso())
+        }
+        Err(e) => format!("error: {e}"),
+      },
+      ":zone" => match ZoneInfo::find(argument) {
+        Some(zone) => {
+          self.parser = self.parser.clone().zone(zone);
+          self.local = zone != Tz::UTC;
+          format!("zone: {}", zone.name())
+        }
+        None => format!("error: unknown zone '{argument}'"),
+      },
+      ":local" => {
+        self.local = !self.local;
+        format!("local time: {}", if self.local { "on" } else { "off" })
+      }
+      ":explain" => {
+        self.explain = !self.explain;
+        format!("explanation: {}", if self.explain { "on" } else { "off" })
+      }
+      ":history" => self
+        .history
+        .iter()
+        .enumerate()
+        .map(|(i, line)| format!("{:4}  {line}", i + 1))
+        .collect::<Vec<_>>()
+        .join("\n"),
+      _ if command.starts_with(':') => {
+        format!("error: unknown command '{command}', see :help")
+      }
+      _ => self.resolve(line),
+    }
+  }
+
+  // the result, the local time and the steps which led to it
+  fn resolve(&self, input: &str) -> String {
+    let explanation = match self.parser().explain(input) {
+      Ok(explanation) => explanation,
+      Err(e) => return format!("error: {e}"),
+    };
+    let mut lines = vec![format!("= {}", to_iso(explanation.utc))];
+    if self.local {
+      let local = explanation.utc.with_timezone(&self.parser.zone);
+      lines.push(format!("  {}", local.to_rfc3339()));
+    }
+    if self.explain {
+      lines.push(format!("  {:?}", explanation.kind));
+      for (i, step) in explanation.steps.iter().enumerate() {
+        lines.push(format!("  {}. {} -> {}", i + 1, step.action, step.value));
+      }
+    }
+    lines.join("\n")
+  }
+
+  fn parser(&self) -> Parser {
+    self
+      .parser
+      .clone()
+      .now(self.pinned.unwrap_or_else(Utc::now))
+  }
+
+  // '!!' is the last line, '!3' the third one
+  fn recall(&self, line: &str) -> Result<Option<String>, String> {
+    let Some(reference) = line.strip_prefix('!') else {
+      return Ok(None);
+    };
+    let entry = if reference == "!" {
+      self.history.last()
+    }
+    else {
+      let n: usize = reference.parse().map_err(|_| {
+        format!("error: expected !! or !<number>, got '{line}'")
+      })?;
+      n.checked_sub(1).and_then(|i| self.history.get(i))
+    };
+    entry
+      .cloned()
+      .map(Some)
+      .ok_or_else(|| format!("error: no history entry '{line}'"))
+  }
+
+  // history is best effort, a read-only home shouldn't stop the session
+  fn remember(&mut self, line: &str) {
+    if self.history.last().is_some_and(|last| last == line) {
+      return;
+    }
+    self.history.push(line.to_string());
+    let Some(path) = &self.history_path else {
+      return;
+    };
+    if self.history.len() > HISTORY_SIZE {
+      self.history.remove(0);
+      let _ = fs::write(path, self.history.join("\n") + "\n");
+      return;
+    }
+    if let Some(dir) = path.parent() {
+      let _ = fs::create_dir_all(dir);
+    }
+    if let Ok(mut file) =
+      OpenOptions::new().create(true).append(true).open(path)
+    {
+      let _ = writeln!(file, "{line}");
+    }
+  }
+}
@@ -0,0 +1,134 @@
+use chrono::prelude::{
+  DateTime, Datelike, NaiveDate, NaiveDateTime, NaiveTime, Timelike,
+};
+use chrono::{Duration, Months, TimeZone};
+
+use crate::chrono_english::lib::{
+  date_result, Ambiguity, DateError, DateResult, WeekStart,
+};
+use crate::chrono_english::types::from_local;
+
+/// A unit instants can be rounded to
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum TimeUnit {
+  Second,
+  Minute,
+  Hour,
+  Day,
+  Week,
+  Month,
+  Year,
+}
+
+/// Which boundary of a unit an instant is moved to
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum Rounding {
+  /// The start of the unit the instant is in
+  Floor,
+  /// The start of the next unit, unless the instant is at a boundary
+  Ceil,
+  /// The closer of both, the later one for ties
+  Round,
+}
+
+impl TimeUnit {
+  /// E.g. "hour"
+  pub fn name(self) -> &'static str {
+    match self {
+      TimeUnit::Second => "second",
+      TimeUnit::Minute => "minute",
+      TimeUnit::Hour => "hour",
+      TimeUnit::Day => "day",
+      TimeUnit::Week => "week",
+      TimeUnit::Month => "month",
+      TimeUnit::Year => "year",
+    }
+  }
+
+  // the start of the unit `naive` is in
+  fn floor(self, naive: NaiveDateTime, week_start: WeekStart) -> NaiveDateTime {
+    let date = naive.date();
+    let midnight = |date: NaiveDate| date.and_time(NaiveTime::MIN);
+    let time = |hour, min, sec| {
+      date.and_time(NaiveTime::from_hms_opt(hour, min, sec).unwrap())
+    };
+    match self {
+      TimeUnit::Second => time(naive.hour(), naive.minute(), naive.second()),
+      TimeUnit::Minute => time(naive.hour(), naive.minute(), 0),
+      TimeUnit::Hour => time(naive.hour(), 0, 0),
+      TimeUnit::Day => midnight(date),
+      TimeUnit::Week => midnight(
+        date - Duration::days(week_start.days_into_week(date.weekday())),
+      ),
+      TimeUnit::Month => midnight(date.with_day(1).unwrap()),
+      TimeUnit::Year => midnight(date.with_ordinal(1).unwrap()),
+    }
+  }
+
+  // the start of the unit after the one starting at `start`
+  fn next(self, start: NaiveDateTime) -> Option<NaiveDateTime> {
+    match self {
+      TimeUnit::Second => start.checked_add_signed(Duration::seconds(1)),
+      TimeUnit::Minute => start.checked_add_signed(Duration::minutes(1)),
+      TimeUnit::Hour => start.checked_add_signed(Duration::hours(1)),
+      TimeUnit::Day => start.checked_add_signed(Duration::days(1)),
+      TimeUnit::Week => start.checked_add_signed(Duration::days(7)),
+      TimeUnit::Month => start.checked_add_months(Months::new(1)),
+      TimeUnit::Year => start.checked_add_months(Months::new(12)),
+    }
+  }
+}
+
+impl std::str::FromStr for TimeUnit {
+  type Err = DateError;
+
+  fn from_str(s: &str) -> DateResult<TimeUnit> {
+    match s {
+      "s" | "sec" | "second" | "seconds" => Ok(TimeUnit::Second),
+      "min" | "minute" | "minutes" => Ok(TimeUnit::Minute),
+      "h" | "hour" | "hours" => Ok(TimeUnit::Hour),
+      "d" | "day" | "days" => Ok(TimeUnit::Day),
+      "w" | "week" | "weeks" => Ok(TimeUnit::Week),
+      "month" | "months" => Ok(TimeUnit::Month),
+      "y" | "year" | "years" => Ok(TimeUnit::Year),
+      _ => date_result(
+        "expected 'second', 'minute', 'hour', 'day', 'week', 'month' \
+         or 'year' as unit",
+      ),
+    }
+  }
+}
+
+impl Rounding {
+  /// E.g. "rounded down"
+  pub fn name(self) -> &'static str {
+    match self {
+      Rounding::Floor => "rounded down",
+      Rounding::Ceil => "rounded up",
+      Rounding::Round => "rounded",
+    }
+  }
+}
+
+/// Move `instant` to a boundary of `unit` in its zone,
+/// e.g. to midnight for days or to the first day of the week for weeks
+pub fn round<Tz: TimeZone>(
+  instant: &DateTime<Tz>,
+  rounding: Rounding,
+  unit: TimeUnit,
+  week_start: WeekStart,
+) -> Option<DateTime<Tz>> {
+  let zone = instant.timezone();
+  let start = unit.floor(instant.naive_local(), week_start);
+  let floor = from_local(&zone, &start, Ambiguity::Earliest)?;
+  if rounding == Rounding::Floor || floor == *instant {
+    return Some(floor);
+  }
+  let ceil = from_local(&zone, &unit.next(start)?, Ambiguity::Earliest)?;
+  if rounding == Rounding::Round
+    && instant.clone() - floor.clone() < ceil.clone() - instant.clone()
+  {
+    return Some(floor);
+  }
+  Some(ceil)
+}
@@ -0,0 +1,178 @@
+use std::fmt;
+
+use chrono::prelude::{DateTime, FixedOffset, Offset, TimeZone, Utc};
+use chrono::SecondsFormat;
+use chrono_tz::{OffsetComponents, OffsetName, Tz, TZ_VARIANTS};
+use serde::{Serialize, Serializer};
+
+use crate::chrono_english::abbreviations::abbreviations;
+use crate::chrono_english::cities::zone_named;
+use crate::parser::ParsedDate;
+
+/// Offset, abbreviation and daylight saving status of a zone at an instant
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ZoneInfo {
+  pub zone: Tz,
+  /// The instant in the zone
+  pub local: DateTime<Tz>,
+  #[serde(serialize_with = "serialize_offset")]
+  pub offset: FixedOffset,
+  /// E.g. `CEST`, or `+03` for zones without a common abbreviation
+  pub abbreviation: String,
+  /// Whether daylight saving time is in effect
+  pub dst: bool,
+}
+
+impl ZoneInfo {
+  /// The state of `zone` at `utc`
+  pub fn at(zone: Tz, utc: DateTime<Utc>) -> ZoneInfo {
+    let local = utc.with_timezone(&zone);
+    let offset = zone.offset_from_utc_datetime(&utc.naive_utc());
+    ZoneInfo {
+      zone,
+      local,
+      offset: offset.fix(),
+      abbreviation: offset
+        .abbreviation()
+        .map(str::to_string)
+        .unwrap_or_else(|| numeric_abbreviation(offset.fix())),
+      dst: !offset.dst_offset().is_zero(),
+    }
+  }
+
+  /// All IANA zones whose name or abbreviation at `utc` contains `filter`,
+  /// ignoring case
+  pub fn list(filter: &str, utc: DateTime<Utc>) -> Vec<ZoneInfo> {
+    let filter = filter.to_lowercase();
+    TZ_VARIANTS
+      .iter()
+      .map(|&zone| ZoneInfo::at(zone, utc))
+      .filter(|info| {
+        info.zone.name().to_lowercase().contains(&filter)
+          || info.abbreviation.to_lowercase().contains(&filter)
+      })
+      .collect()
+  }
+
+  /// Look up a zone by its IANA name or a city like `Tokyo`, ignoring case
+  pub fn find(name: &str) -> Option<Tz> {
+    zone_named(name)
+  }
+
+  /// Zones which use an abbreviation like `IST`,
+  /// with the offset it stands for in each of them
+  pub fn using_abbreviation(abbreviation: &str) -> Vec<(Tz, FixedOffset)> {
+    abbreviations()
+      .get(&abbreviation.to_ascii_uppercase())
+      .map(|uses| {
+        uses
+          .iter()
+          .map(|usage| (usage.zone, usage.offset))
+          .collect()
+      })
+      .unwrap_or_default()
+  }
+}
+
+// the IANA database names zones without a common abbreviation
+// by their offset, like '+03' or '+0530'
+fn numeric_abbreviation(offset: FixedOffset) -> String {
+  let seconds = offset.local_minus_utc();
+  let sign = if seconds < 0 { '-' } else { '+' };
+  let (hours, minutes) = (seconds.abs() / 3600, seconds.abs() % 3600 / 60);
+  if minutes == 0 {
+    format!("{sign}{hours:02}")
+  }
+  else {
+    format!("{sign}{hours:02}{minutes:02}")
+  }
+}
+
+fn serialize_offset<S: Serializer>(
+  offset: &FixedOffset,
+  serializer: S,
+) -> Result<S::Ok, S::Error> {
+  serializer.collect_str(offset)
+}
+
+/// A parsed instant as seen in several zones, like on a world clock
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct WorldClock {
+  pub utc: DateTime<Utc>,
+  pub zones: Vec<ZoneTime>,
+}
+
+/// The state of one zone of a [`WorldClock`]
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ZoneTime {
+  #[serde(flatten)]
+  pub info: ZoneInfo,
+  /// Days between the date in the zone and the date in the zone of the
+  /// parser, e.g. `1` when it is already tomorrow there
+  pub day_change: i64,
+}
+
+impl ParsedDate {
+  /// The instant in each of `zones`, in the given order
+  pub fn world_clock(&self, zones: &[Tz]) -> WorldClock {
+    let date = self.local.date_naive();
+    WorldClock {
+      utc: self.utc,
+      zones: zones
+        .iter()
+        .map(|&zone| {
+          let info = ZoneInfo::at(zone, self.utc);
+          let day_change = (info.local.date_naive() - date).num_days();
+          ZoneTime { info, day_change }
+        })
+        .collect(),
+    }
+  }
+}
+
+impl fmt::Display for WorldClock {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    let width = self.zones.iter().map(|time| time.info.zone.name().len());
+    let width = width.max().unwrap_or_default();
+    for (i, time) in self.zones.iter().enumerate() {
+      if i > 0 {
+        writeln!(f)?;
+      }
+      let info = &time.info;
+      let mut marks = Vec::new();
+      if info.dst {
+        marks.push("DST".to_string());
+      }
+      match time.day_change {
+        0 => (),
+        1 => marks.push("+1 day".to_string()),
+        -1 => marks.push("-1 day".to_string()),
+        days => marks.push(format!("{days:+} days")),
+      }
+      let line = format!(
+        "{:width$}  {}  {}  {:5}  {}",
+        info.zone.name(),
+        info.local.format("%a %Y-%m-%d %H:%M"),
+        info.offset,
+        info.abbreviation,
+        marks.join(", "),
+      );
+      write!(f, "{}", line.trim_end())?;
+    }
+    Ok(())
+  }
+}
+
+impl fmt::Display for ZoneInfo {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    writeln!(f, "Zone:         {}", self.zone.name())?;
+    writeln!(
+      f,
+      "Local time:   {}",
+      self.local.to_rfc3339_opts(SecondsFormat::Secs, false)
+    )?;
+    writeln!(f, "Offset:       {}", self.offset)?;
+    writeln!(f, "Abbreviation: {}", self.abbreviation)?;
+    write!(f, "DST:          {}", if self.dst { "yes" } else { "no" })
+  }
+}
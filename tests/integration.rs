@@ -1,5 +1,5 @@
 use chrono::{
-  prelude::{NaiveDate, NaiveDateTime},
+  prelude::{NaiveDate, NaiveDateTime, NaiveTime},
   DateTime, Datelike, TimeZone, Utc,
 };
 use tu::*;
@@ -69,6 +69,10 @@ fn test_parse_date_string() {
     ("tomorrow", (2024, 1, 1, 9, 34, 52), (2024, 1, 2, 9, 34, 52)),
     ("tmr", (2024, 1, 1, 9, 34, 52), (2024, 1, 2, 9, 34, 52)),
     ("tmrw", (2024, 1, 1, 9, 34, 52), (2024, 1, 2, 9, 34, 52)),
+    ("thurs", dt!(2024, 1, 1), dt!(2024, 1, 4)),
+    ("next tues", dt!(2024, 1, 1), dt!(2024, 1, 2)),
+    ("wednes", dt!(2024, 1, 1), dt!(2024, 1, 3)),
+    ("satur", dt!(2024, 1, 1), dt!(2024, 1, 6)),
     (
       "tomorrow 4pm",
       (2024, 1, 1, 9, 34, 52),
@@ -270,6 +274,19 @@ fn test_parse_date_string() {
   for (input, now, expected) in tests.iter() {
     execute_test(max_test_len, input, *now, *expected);
   }
+
+  // Weekdays are beginnings of their names
+  let now = Utc.from_utc_datetime(&tup_to_naive_date(dt!(2024, 1, 1)));
+  assert!(parse_date_args(&["month".to_string()], now).is_err());
+
+  // Dates out of range are errors
+  for input in ["99999999 days", "in 1000000 years", "1000000000 weeks ago"] {
+    assert_eq!(
+      parse_date_args(&[input.to_string()], now).map_err(|e| e.to_string()),
+      Err("date out of range".to_string()),
+      "{input}"
+    );
+  }
 }
 
 #[test]
@@ -335,3 +352,1436 @@ fn test_unix_timestamp_edge_cases() {
     }
   }
 }
+
+#[test]
+fn test_parser_builder() {
+  let now = Utc.from_utc_datetime(&tup_to_naive_date(dt!(2024, 1, 1)));
+  let parser = Parser::new().now(now);
+
+  let parsed = parser.parse("tomorrow 4pm").unwrap();
+  assert_eq!(parsed.to_iso(), "2024-01-02T16:00:00Z");
+  assert_eq!(parsed.kind, InputKind::Natural);
+  assert_eq!(
+    parser.parse("2024-04-10T13:31:46+04:00").unwrap().kind,
+    InputKind::Rfc3339
+  );
+  assert_eq!(
+    parser.parse("1740599117").unwrap().kind,
+    InputKind::UnixTimestamp
+  );
+
+  // Expressions are interpreted in the zone of the parser
+  let berlin = parser.clone().zone(Tz::Europe__Berlin);
+  let parsed = berlin.parse("tomorrow 4pm").unwrap();
+  assert_eq!(parsed.to_iso(), "2024-01-02T15:00:00Z");
+  assert_eq!(parsed.local.to_rfc3339(), "2024-01-02T16:00:00+01:00");
+
+  // Dialect decides the order of day and month
+  assert_eq!(
+    parser.parse("3/4/2024").unwrap().to_iso(),
+    "2024-03-04T00:00:00Z"
+  );
+  assert_eq!(
+    parser
+      .clone()
+      .dialect(Dialect::Uk)
+      .parse("3/4/2024")
+      .unwrap()
+      .to_iso(),
+    "2024-04-03T00:00:00Z"
+  );
+
+  // Strict mode rejects trailing text
+  assert!(parser.parse("14 december 11:20 at home").is_ok());
+  assert!(parser
+    .clone()
+    .strict(true)
+    .parse("14 december 11:20 at home")
+    .is_err());
+  assert!(parser
+    .clone()
+    .strict(true)
+    .parse("14 december 11:20")
+    .is_ok());
+
+  // Local times skipped or repeated by daylight saving transitions
+  let skipped = "2024-03-31 02:30";
+  let repeated = "2024-10-27 02:30";
+  assert_eq!(
+    berlin.parse(skipped).unwrap().to_iso(),
+    "2024-03-31T01:30:00Z"
+  );
+  assert!(berlin
+    .clone()
+    .ambiguity(Ambiguity::Reject)
+    .parse(skipped)
+    .is_err());
+  assert_eq!(
+    berlin.parse(repeated).unwrap().to_iso(),
+    "2024-10-27T00:30:00Z"
+  );
+  assert_eq!(
+    berlin
+      .clone()
+      .ambiguity(Ambiguity::Latest)
+      .parse(repeated)
+      .unwrap()
+      .to_iso(),
+    "2024-10-27T01:30:00Z"
+  );
+
+  assert!(parse_print(now, "no date at all").is_err());
+}
+
+#[test]
+fn test_explain() {
+  let now = Utc.from_utc_datetime(&tup_to_naive_date(dt!(2024, 1, 4)));
+  let parser = Parser::new().now(now);
+
+  let explanation = parser.explain("next friday 4pm").unwrap();
+  assert_eq!(explanation.kind, InputKind::Natural);
+  assert_eq!(to_iso(explanation.utc), "2024-01-05T16:00:00Z");
+  let actions: Vec<&str> = explanation
+    .steps
+    .iter()
+    .map(|step| step.action.as_str())
+    .collect();
+  assert_eq!(
+    actions,
+    [
+      "reference time",
+      "Fri of the current week",
+      "set time 16:00:00",
+      "converted to UTC",
+    ]
+  );
+
+  let json = serde_json::to_value(&explanation).unwrap();
+  assert_eq!(
+    json["spec"]["date"]["FromName"]["WeekDay"]["direct"],
+    serde_json::json!("Next")
+  );
+  assert_eq!(json["spec"]["time"]["hour"], serde_json::json!(16));
+  let text = explanation.to_string();
+  assert!(text.contains("Date:       weekday: Friday, direction: next\n"));
+  assert!(text.contains("Time:       time: 16:00:00\n"));
+
+  let text = parser.explain("2 weeks").unwrap().to_string();
+  assert!(text.contains("Date:       amount: 2, unit: 7 days\n"));
+  let text = parser.explain("5 may 09:00 PST").unwrap().to_string();
+  assert!(text.contains("Date:       day: 5, month: May\n"));
+  assert!(text.contains("Time:       time: 09:00:00, abbreviation: PST\n"));
+
+  let explanation = parser.explain("1740599117").unwrap();
+  assert_eq!(explanation.kind, InputKind::UnixTimestamp);
+  assert!(explanation.spec.is_none());
+  assert_eq!(explanation.steps[0].action, "seconds since the Unix epoch");
+}
+
+#[test]
+fn test_direction_policy() {
+  // Thursday
+  let now = Utc.from_utc_datetime(&tup_to_naive_date((2024, 1, 4, 10, 0, 0)));
+  let closest = Parser::new()
+    .now(now)
+    .direction_policy(DirectionPolicy::Closest);
+  let period = Parser::new()
+    .now(now)
+    .direction_policy(DirectionPolicy::Period);
+
+  let tests = [
+    (&closest, "friday", dt!(2024, 1, 5)),
+    (&closest, "this friday", dt!(2024, 1, 5)),
+    (&closest, "next friday", dt!(2024, 1, 5)),
+    (&closest, "last friday", dt!(2023, 12, 29)),
+    (&closest, "next thursday", dt!(2024, 1, 11)),
+    (&closest, "this thursday", dt!(2024, 1, 4)),
+    (&closest, "last thursday", dt!(2023, 12, 28)),
+    (&closest, "thursday", dt!(2024, 1, 11)),
+    (&closest, "this march", dt!(2024, 3, 1)),
+    (&closest, "next march", dt!(2024, 3, 1)),
+    (&closest, "last march", dt!(2023, 3, 1)),
+    (&closest, "next 5 may", dt!(2024, 5, 5)),
+    (&period, "friday", dt!(2024, 1, 5)),
+    (&period, "this friday", dt!(2024, 1, 5)),
+    (&period, "next friday", dt!(2024, 1, 12)),
+    (&period, "last friday", dt!(2023, 12, 29)),
+    (&period, "this monday", dt!(2024, 1, 1)),
+    (&period, "next monday", dt!(2024, 1, 8)),
+    (&period, "this thursday", dt!(2024, 1, 4)),
+    (&period, "last thursday", dt!(2023, 12, 28)),
+    (&period, "this march", dt!(2024, 3, 1)),
+    (&period, "next march", dt!(2025, 3, 1)),
+    (&period, "last march", dt!(2023, 3, 1)),
+    (&period, "next 5 may", dt!(2025, 5, 5)),
+  ];
+
+  for (parser, input, expected) in tests {
+    assert_eq!(
+      parser.parse(input).unwrap().utc.naive_utc(),
+      tup_to_naive_date(expected),
+      "Failed for input: {input}"
+    );
+  }
+
+  // UK English defaults to whole periods
+  let uk = Parser::new().now(now).dialect(Dialect::Uk);
+  assert_eq!(
+    uk.parse("next friday").unwrap().to_iso(),
+    "2024-01-12T00:00:00Z"
+  );
+
+  // Months and days of a month like weekdays, on 2024-05-14
+  let now = Utc.from_utc_datetime(&tup_to_naive_date((2024, 5, 14, 10, 0, 0)));
+  let closest = Parser::new()
+    .now(now)
+    .direction_policy(DirectionPolicy::Closest);
+  let tests = [
+    ("next 14 may", dt!(2025, 5, 14)),
+    ("this 14 may", dt!(2024, 5, 14)),
+    ("last 14 may", dt!(2023, 5, 14)),
+    ("next 15 may", dt!(2024, 5, 15)),
+    ("last 13 may", dt!(2024, 5, 13)),
+    ("this 13 may", dt!(2025, 5, 13)),
+    ("next may", dt!(2025, 5, 1)),
+    ("this may", dt!(2024, 5, 1)),
+    ("last may", dt!(2023, 5, 1)),
+    ("next june", dt!(2024, 6, 1)),
+    ("last april", dt!(2024, 4, 1)),
+    ("this april", dt!(2025, 4, 1)),
+  ];
+  for (input, expected) in tests {
+    assert_eq!(
+      closest.parse(input).unwrap().utc.naive_utc(),
+      tup_to_naive_date(expected),
+      "Failed for input: {input}"
+    );
+  }
+}
+
+#[test]
+fn test_week_start() {
+  // Thursday
+  let now = Utc.from_utc_datetime(&tup_to_naive_date((2024, 1, 4, 10, 0, 0)));
+  let monday = Parser::new().now(now);
+  let sunday = monday.clone().week_start(WeekStart::Sunday);
+  let saturday = monday.clone().week_start(WeekStart::Saturday);
+
+  let tests = [
+    (&monday, "this week", dt!(2024, 1, 1)),
+    (&monday, "start of week", dt!(2024, 1, 1)),
+    (&monday, "next week", dt!(2024, 1, 8)),
+    (&monday, "start of last week", dt!(2023, 12, 25)),
+    (&monday, "week 1", dt!(2024, 1, 1)),
+    (&monday, "week 12 2025", dt!(2025, 3, 17)),
+    (&monday, "2025-W12", dt!(2025, 3, 17)),
+    (&monday, "2025-W12-3", dt!(2025, 3, 19)),
+    (&monday, "next week 9:00", (2024, 1, 8, 9, 0, 0)),
+    (&sunday, "this week", dt!(2023, 12, 31)),
+    (&sunday, "next week", dt!(2024, 1, 7)),
+    (&sunday, "week 1", dt!(2023, 12, 31)),
+    (&sunday, "week 12 2025", dt!(2025, 3, 16)),
+    // ISO week dates don't depend on the week start
+    (&sunday, "2025-W12", dt!(2025, 3, 17)),
+    (&saturday, "this week", dt!(2023, 12, 30)),
+    (&saturday, "next week", dt!(2024, 1, 6)),
+  ];
+
+  for (parser, input, expected) in tests {
+    assert_eq!(
+      parser.parse(input).unwrap().utc.naive_utc(),
+      tup_to_naive_date(expected),
+      "Failed for input: {input}"
+    );
+  }
+
+  // Weeks with whole periods follow the week start
+  let period = |parser: &Parser| {
+    parser
+      .clone()
+      .direction_policy(DirectionPolicy::Period)
+      .parse("this sunday")
+      .unwrap()
+      .to_iso()
+  };
+  assert_eq!(period(&monday), "2024-01-07T00:00:00Z");
+  assert_eq!(period(&sunday), "2023-12-31T00:00:00Z");
+
+  assert!(monday.parse("week 54").is_err());
+  assert!(sunday.parse("week 53 2024").is_err());
+
+  let dec_29 = NaiveDate::from_ymd_opt(2024, 12, 29).unwrap();
+  assert_eq!(WeekStart::Monday.week_number(dec_29), (2024, 52));
+  assert_eq!(WeekStart::Sunday.week_number(dec_29), (2025, 1));
+  assert_eq!("sun".parse::<WeekStart>().unwrap(), WeekStart::Sunday);
+}
+
+#[test]
+fn test_times_of_day() {
+  let tests = [
+    ("noon", (2024, 1, 1, 12, 0, 0)),
+    ("tomorrow noon", (2024, 1, 2, 12, 0, 0)),
+    ("tomorrow at noon", (2024, 1, 2, 12, 0, 0)),
+    ("at noon", (2024, 1, 1, 12, 0, 0)),
+    ("at 5", (2024, 1, 1, 5, 0, 0)),
+    ("at 5pm", (2024, 1, 1, 17, 0, 0)),
+    ("noon tomorrow", (2024, 1, 2, 12, 0, 0)),
+    ("friday midnight", (2024, 1, 5, 0, 0, 0)),
+    ("midnight friday", (2024, 1, 5, 0, 0, 0)),
+    ("tonight", (2024, 1, 1, 21, 0, 0)),
+    ("tonight 11pm", (2024, 1, 1, 23, 0, 0)),
+    ("this evening", (2024, 1, 1, 18, 0, 0)),
+    ("tomorrow morning", (2024, 1, 2, 9, 0, 0)),
+    ("EOD", (2024, 1, 1, 17, 0, 0)),
+    ("cob tomorrow", (2024, 1, 2, 17, 0, 0)),
+  ];
+  let max_test_len = tests.iter().map(|(s, _)| s.len()).max().unwrap_or(0);
+
+  for (input, expected) in tests {
+    execute_test(max_test_len, input, (2024, 1, 1, 8, 0, 0), expected);
+  }
+
+  let now = Utc.from_utc_datetime(&tup_to_naive_date(dt!(2024, 1, 1)));
+  let parser = Parser::new()
+    .now(now)
+    .time_of_day("morning", NaiveTime::from_hms_opt(7, 30, 0).unwrap())
+    .time_of_day("standup", NaiveTime::from_hms_opt(9, 15, 0).unwrap());
+  assert_eq!(
+    parser.parse("tomorrow morning").unwrap().to_iso(),
+    "2024-01-02T07:30:00Z"
+  );
+  assert_eq!(
+    parser.parse("friday standup").unwrap().to_iso(),
+    "2024-01-05T09:15:00Z"
+  );
+}
+
+#[test]
+fn test_spelled_out_numbers() {
+  let now = (2024, 1, 1, 8, 0, 0);
+  let tests = [
+    ("in two weeks", (2024, 1, 15, 8, 0, 0)),
+    ("three days ago", (2023, 12, 29, 8, 0, 0)),
+    ("twenty-one days", (2024, 1, 22, 8, 0, 0)),
+    ("a couple of days", (2024, 1, 3, 8, 0, 0)),
+    ("in a couple of days", (2024, 1, 3, 8, 0, 0)),
+    ("in a dozen days", (2024, 1, 13, 8, 0, 0)),
+    ("in a hundred days", (2024, 4, 10, 8, 0, 0)),
+    ("in a few days", (2024, 1, 4, 8, 0, 0)),
+    ("in a half hour", (2024, 1, 1, 8, 30, 0)),
+    ("in a quarter of an hour", (2024, 1, 1, 8, 15, 0)),
+    ("a hundred and five minutes", (2024, 1, 1, 9, 45, 0)),
+    ("half an hour", (2024, 1, 1, 8, 30, 0)),
+    ("in half an hour", (2024, 1, 1, 8, 30, 0)),
+    ("three quarters of an hour", (2024, 1, 1, 8, 45, 0)),
+    ("two and a half hours", (2024, 1, 1, 10, 30, 0)),
+    ("in an hour and a half", (2024, 1, 1, 9, 30, 0)),
+    ("1.5 hours ago", (2024, 1, 1, 6, 30, 0)),
+    ("a quarter past nine", (2024, 1, 1, 9, 15, 0)),
+    ("half past 3", (2024, 1, 1, 3, 30, 0)),
+    ("quarter to five pm", (2024, 1, 1, 16, 45, 0)),
+    ("10 past 3 pm", (2024, 1, 1, 15, 10, 0)),
+    ("half past 3 tomorrow", (2024, 1, 2, 3, 30, 0)),
+    ("tomorrow at nine", (2024, 1, 2, 9, 0, 0)),
+    ("friday at half past nine pm", (2024, 1, 5, 21, 30, 0)),
+  ];
+  let max_test_len = tests.iter().map(|(s, _)| s.len()).max().unwrap_or(0);
+
+  for (input, expected) in tests {
+    execute_test(max_test_len, input, now, expected);
+  }
+
+  let now = Utc.from_utc_datetime(&tup_to_naive_date(now));
+  assert!(parse_date_args(&["two".to_string()], now).is_err());
+  assert!(parse_date_args(&["half".to_string()], now).is_err());
+  for input in ["99 past 3", "60 past 3", "75 to 5"] {
+    assert_eq!(
+      parse_date_args(&[input.to_string()], now).map_err(|e| e.to_string()),
+      Err("expected minutes from 0 to 59".to_string()),
+      "{input}"
+    );
+  }
+}
+
+#[test]
+fn test_languages() {
+  let now = Utc.from_utc_datetime(&tup_to_naive_date((2024, 1, 1, 8, 0, 0)));
+  let tests = [
+    ("de", "morgen 15 Uhr", "2024-01-02T15:00:00Z"),
+    ("de", "morgen um 15:30 Uhr", "2024-01-02T15:30:00Z"),
+    ("de", "um 15 Uhr", "2024-01-01T15:00:00Z"),
+    ("de", "um drei Uhr", "2024-01-01T03:00:00Z"),
+    ("de", "in 3 Tagen", "2024-01-04T08:00:00Z"),
+    ("de", "vor drei Tagen", "2023-12-29T08:00:00Z"),
+    ("de", "in einer halben Stunde", "2024-01-01T08:30:00Z"),
+    ("de", "nächsten Freitag", "2024-01-05T00:00:00Z"),
+    ("de", "nächste Woche", "2024-01-08T00:00:00Z"),
+    ("de", "übermorgen halb drei", "2024-01-03T02:30:00Z"),
+    ("de", "heute Viertel vor drei", "2024-01-01T02:45:00Z"),
+    ("de", "heute abend", "2024-01-01T18:00:00Z"),
+    ("de", "3. März 2025", "2025-03-03T00:00:00Z"),
+    ("de", "24.12.2025", "2025-12-24T00:00:00Z"),
+    ("de", "9/11/2025", "2025-11-09T00:00:00Z"),
+    ("fr", "dans 3 jours", "2024-01-04T08:00:00Z"),
+    ("fr", "il y a trois heures", "2024-01-01T05:00:00Z"),
+    ("fr", "demain à 15h30", "2024-01-02T15:30:00Z"),
+    ("fr", "vendredi prochain", "2024-01-05T00:00:00Z"),
+    ("fr", "la semaine prochaine", "2024-01-08T00:00:00Z"),
+    ("fr", "après-demain à midi", "2024-01-03T12:00:00Z"),
+    (
+      "fr",
+      "demain à trois heures moins le quart",
+      "2024-01-02T02:45:00Z",
+    ),
+    ("fr", "demain quinze heures trente", "2024-01-02T15:30:00Z"),
+    ("fr", "dans un quart d'heure", "2024-01-01T08:15:00Z"),
+    ("fr", "le 3 mars 2025 à 15h", "2025-03-03T15:00:00Z"),
+    ("fr", "15 heures", "2024-01-01T15:00:00Z"),
+    ("fr", "à 15h", "2024-01-01T15:00:00Z"),
+    ("fr", "à trois heures et quart", "2024-01-01T03:15:00Z"),
+    ("fr", "dans 15 heures", "2024-01-01T23:00:00Z"),
+    ("fr", "il y a 3 heures", "2024-01-01T05:00:00Z"),
+    ("fr", "trois heures et quart", "2024-01-01T03:15:00Z"),
+    ("es", "mañana a las 10", "2024-01-02T10:00:00Z"),
+    ("es", "mañana a las tres y media", "2024-01-02T03:30:00Z"),
+    ("es", "dentro de 3 días", "2024-01-04T08:00:00Z"),
+    ("es", "hace una hora y media", "2024-01-01T06:30:00Z"),
+    ("es", "el viernes que viene", "2024-01-05T00:00:00Z"),
+    ("es", "pasado mañana por la mañana", "2024-01-03T09:00:00Z"),
+    ("es", "3 de marzo", "2024-03-03T00:00:00Z"),
+    ("es", "las tres menos cuarto", "2024-01-01T02:45:00Z"),
+    ("es", "a las 10", "2024-01-01T10:00:00Z"),
+  ];
+
+  for (code, input, expected) in tests {
+    let lang = language::by_code(code).unwrap();
+    let parser = Parser::new().now(now).language(lang);
+    let result = parser.parse(input).map(|parsed| parsed.to_iso());
+    assert_eq!(result.as_deref(), Ok(expected), "{code}: {input}");
+  }
+
+  // English words are not understood in other languages
+  let german = Parser::new().now(now).language(&language::GERMAN);
+  assert!(german.parse("tomorrow").is_err());
+  // An explicit dialect wins over the day-first convention of a language
+  assert_eq!(
+    german
+      .dialect(Dialect::Us)
+      .parse("9/11/2025")
+      .unwrap()
+      .to_iso(),
+    "2025-09-11T00:00:00Z"
+  );
+  assert!(language::by_code("xx").is_none());
+}
+
+#[test]
+fn test_config() {
+  let config = Config::parse(
+    r#"
+      zone = "Europe/Berlin"
+      dialect = "uk"
+      format = "local"
+
+      [aliases]
+      eod = "today 17:00"
+      "sprint end" = "next friday 18:00"
+      ship = "release day"
+
+      [dates]
+      "release day" = "2025-03-14 10:00"
+    "#,
+  )
+  .unwrap();
+  let now = Utc.from_utc_datetime(&tup_to_naive_date((2024, 1, 1, 8, 0, 0)));
+  let parser = config.apply(Parser::new().now(now)).unwrap();
+  let parse = |input| parser.parse(input).unwrap().to_iso();
+
+  assert_eq!(config.format(), Ok(Some(Format::Local)));
+  assert_eq!(parse("eod"), "2024-01-01T16:00:00Z");
+  // UK English reads "next friday" as the Friday of next week
+  assert_eq!(parse("Sprint  End"), "2024-01-12T17:00:00Z");
+  assert_eq!(parse("release day"), "2025-03-14T09:00:00Z");
+  assert_eq!(parse("ship"), "2025-03-14T09:00:00Z");
+  assert_eq!(parse("9/11/2025"), "2025-11-08T23:00:00Z");
+  assert_eq!(parser.parse("ship").unwrap().kind, InputKind::NamedDate);
+  assert_eq!(
+    parser.parse("eod").unwrap().format(Format::Local),
+    "2024-01-01T17:00:00+01:00"
+  );
+
+  assert!(Config::parse("zon = 'UTC'").is_err());
+  let unknown_zone = Config::parse("zone = 'Mars/Olympus'").unwrap();
+  assert!(unknown_zone.apply(Parser::new()).is_err());
+}
+
+#[test]
+fn test_interpretations() {
+  let now = Utc.from_utc_datetime(&tup_to_naive_date((2024, 1, 1, 8, 0, 0)));
+  let parser = Parser::new().now(now);
+  let readings = |input| {
+    parser
+      .interpretations(input)
+      .unwrap()
+      .into_iter()
+      .map(|i| (i.rank, i.date.to_iso(), i.rule))
+      .collect::<Vec<_>>()
+  };
+  let reading =
+    |rank, iso: &str, rule: &str| (rank, iso.to_string(), rule.to_string());
+
+  assert_eq!(
+    readings("03/04/05"),
+    [
+      reading(1, "2005-03-04T00:00:00Z", "month/day (US)"),
+      reading(2, "2005-04-03T00:00:00Z", "day/month (UK)"),
+      reading(3, "2003-04-05T00:00:00Z", "year/month/day"),
+    ]
+  );
+  assert_eq!(
+    readings("next friday"),
+    [
+      reading(1, "2024-01-05T00:00:00Z", "next/last as the closest match"),
+      reading(
+        2,
+        "2024-01-12T00:00:00Z",
+        "next/last as the following/previous week or year"
+      ),
+    ]
+  );
+  assert_eq!(
+    readings("13"),
+    [
+      reading(1, "1970-01-01T00:00:13Z", "seconds since the Unix epoch"),
+      reading(2, "2024-01-01T13:00:00Z", "hour of today"),
+      reading(3, "2024-01-13T00:00:00Z", "day of this month"),
+      reading(
+        4,
+        "1970-01-01T00:00:00.013Z",
+        "milliseconds since the Unix epoch"
+      ),
+    ]
+  );
+  assert_eq!(
+    readings("1740599117000")[0].2,
+    "milliseconds since the Unix epoch"
+  );
+  assert_eq!(
+    readings("1740599117000")[1].2,
+    "seconds since the Unix epoch"
+  );
+
+  let berlin = Parser::new().now(now).zone(Tz::Europe__Berlin);
+  let repeated = berlin.interpretations("2025-10-26 2:30").unwrap();
+  assert_eq!(repeated.len(), 2);
+  assert_eq!(repeated[1].date.to_iso(), "2025-10-26T01:30:00Z");
+
+  assert_eq!(
+    readings("tomorrow"),
+    [reading(
+      1,
+      "2024-01-02T08:00:00Z",
+      "natural language expression"
+    )]
+  );
+  assert!(parser.interpretations("foo").is_err());
+}
+
+#[test]
+fn test_epoch_timestamps() {
+  let now = Utc.from_utc_datetime(&tup_to_naive_date(dt!(2024, 1, 1)));
+  let parser = Parser::new().now(now);
+  let iso = |parser: &Parser, input| parser.parse(input).unwrap().to_iso();
+
+  assert_eq!(iso(&parser, "1753438921.377"), "2025-07-25T10:22:01.377Z");
+  assert_eq!(iso(&parser, "1753438921.5"), "2025-07-25T10:22:01.500Z");
+  assert_eq!(
+    iso(&parser, "1753438921377123"),
+    "2025-07-25T10:22:01.377123Z"
+  );
+  assert_eq!(
+    iso(&parser, "1753438921377123456"),
+    "2025-07-25T10:22:01.377123456Z"
+  );
+  assert_eq!(iso(&parser, "-86400"), "1969-12-31T00:00:00Z");
+  assert_eq!(iso(&parser, "@-1.5"), "1969-12-31T23:59:58.500Z");
+  assert_eq!(iso(&parser, "@1753438921"), "2025-07-25T10:22:01Z");
+  assert_eq!(iso(&parser, "@42"), "1970-01-01T00:00:42Z");
+  assert_eq!(
+    parser.parse("@1753438921.377").unwrap().kind,
+    InputKind::UnixTimestamp
+  );
+
+  // Short decimals are no timestamps, unless marked as such
+  assert!(parser.parse("11.20").is_err());
+  assert_eq!(iso(&parser, "@11.20"), "1970-01-01T00:00:11.200Z");
+
+  // An explicit unit overrides the guess from the number of digits
+  let millis = parser.clone().epoch_unit(EpochUnit::Milliseconds);
+  assert_eq!(iso(&millis, "1753438921"), "1970-01-21T07:03:58.921Z");
+  assert_eq!(iso(&millis, "1753438921377"), "2025-07-25T10:22:01.377Z");
+  let seconds = parser.clone().epoch_unit("s".parse().unwrap());
+  assert_eq!(iso(&seconds, "1000000000000"), "+33658-09-27T01:46:40Z");
+  assert!("minutes".parse::<EpochUnit>().is_err());
+}
+
+#[test]
+fn test_serial_dates() {
+  let parser = Parser::new();
+  let iso = |input| parser.parse(input).unwrap().to_iso();
+
+  assert_eq!(iso("excel:45678.5"), "2025-01-21T12:00:00Z");
+  assert_eq!(iso("jd:2460000.5"), "2023-02-25T00:00:00Z");
+  assert_eq!(iso("jd:2440587.5"), "1970-01-01T00:00:00Z");
+  assert_eq!(iso("filetime:133000000000000000"), "2022-06-18T04:26:40Z");
+  assert_eq!(iso("cocoa:700000000"), "2023-03-08T20:26:40Z");
+  assert_eq!(iso("mac:3800000000"), "2024-05-31T11:33:20Z");
+  // GPS time is 18 leap seconds ahead of UTC since 2017
+  assert_eq!(iso("gps:1400000000"), "2024-05-17T16:53:02Z");
+  assert_eq!(iso("gps:0"), "1980-01-06T00:00:00Z");
+  assert_eq!(parser.parse("excel:45678").unwrap().kind, InputKind::Serial);
+  assert_eq!(
+    parser.parse("excel:abc").unwrap_err().to_string(),
+    "expected a number after 'excel:'"
+  );
+  assert_eq!(
+    parser
+      .parse("excel:999999999999999999")
+      .unwrap_err()
+      .to_string(),
+    "excel value out of range"
+  );
+
+  let date = parser.parse("2025-07-25T10:22:01Z").unwrap();
+  let format = |name: &str| date.format(name.parse().unwrap());
+  assert_eq!(format("excel"), "45863.43195602");
+  assert_eq!(format("jd"), "2460881.93195602");
+  assert_eq!(format("filetime"), "133979125210000000");
+  assert_eq!(format("gps"), "1437474139");
+  assert_eq!(format("cocoa"), "775131721");
+  assert_eq!(format("mac"), "3836283721");
+
+  // Formats and tags round-trip
+  let noon = parser.parse("2025-01-21 12:00").unwrap();
+  assert_eq!(noon.format(Format::Excel), "45678.5");
+  for name in ["excel", "jd", "filetime", "gps", "cocoa", "mac"] {
+    let tagged = format!("{name}:{}", format(name));
+    assert_eq!(
+      parser.parse(&tagged).unwrap().utc.timestamp(),
+      date.utc.timestamp(),
+      "{tagged}"
+    );
+  }
+}
+
+#[test]
+fn test_layouts() {
+  let now = Utc.from_utc_datetime(&tup_to_naive_date((2024, 3, 16, 12, 0, 0)));
+  let parser = Parser::new().now(now);
+
+  let corpus = [
+    // Apache and NGINX access logs
+    ("10/Oct/2000:13:55:36 -0700", "2000-10-10T20:55:36Z"),
+    ("[10/Oct/2000:13:55:36 -0700]", "2000-10-10T20:55:36Z"),
+    // syslog, the most recent such time
+    ("Mar 10 13:55:36", "2024-03-10T13:55:36Z"),
+    ("Oct  1 13:55:36", "2023-10-01T13:55:36Z"),
+    ("Mar 16 08:00:00.250", "2024-03-16T08:00:00.250Z"),
+    // asctime and the HTTP-date forms
+    ("Wed Jun 30 21:49:08 1993", "1993-06-30T21:49:08Z"),
+    ("Sun Nov  6 08:49:37 1994", "1994-11-06T08:49:37Z"),
+    ("Sunday, 06-Nov-94 08:49:37 GMT", "1994-11-06T08:49:37Z"),
+    ("Sun, 06 Nov 1994 08:49:37 GMT", "1994-11-06T08:49:37Z"),
+    // Go's time.Time.String()
+    (
+      "2006-01-02 15:04:05.999 -0700 MST",
+      "2006-01-02T22:04:05.999Z",
+    ),
+    (
+      "2009-11-10 23:00:00 +0000 UTC m=+0.000000001",
+      "2009-11-10T23:00:00Z",
+    ),
+    // Python
+    ("2024-01-15 10:30:45,123", "2024-01-15T10:30:45.123Z"),
+    (
+      "datetime.datetime(2024, 1, 15, 10, 30, 45, 123456)",
+      "2024-01-15T10:30:45.123456Z",
+    ),
+    (
+      "datetime.datetime(2024, 1, 15, 10, 30, tzinfo=datetime.timezone.utc)",
+      "2024-01-15T10:30:00Z",
+    ),
+    (
+      "datetime.datetime(2024, 7, 15, 10, 30, \
+       tzinfo=zoneinfo.ZoneInfo(key='Europe/Berlin'))",
+      "2024-07-15T08:30:00Z",
+    ),
+    (
+      "datetime.datetime(2024, 1, 15, 10, 30, tzinfo=datetime.timezone(\
+       datetime.timedelta(days=-1, seconds=68400)))",
+      "2024-01-15T15:30:00Z",
+    ),
+    ("datetime.date(2024, 1, 15)", "2024-01-15T00:00:00Z"),
+    (
+      "2024-01-15 10:30:45.123456+00:00",
+      "2024-01-15T10:30:45.123456Z",
+    ),
+  ];
+  for (input, expected) in corpus {
+    let parsed = parser
+      .parse(input)
+      .unwrap_or_else(|e| panic!("{input}: {e}"));
+    assert_eq!(parsed.to_iso(), expected, "{input}");
+  }
+
+  assert_eq!(
+    parser.parse("10/Oct/2000:13:55:36 -0700").unwrap().kind,
+    InputKind::Layout
+  );
+  // Times without an offset are in the zone of the parser
+  assert_eq!(
+    parser
+      .clone()
+      .zone(Tz::Europe__Berlin)
+      .parse("Wed Jun 30 21:49:08 1993")
+      .unwrap()
+      .to_iso(),
+    "1993-06-30T19:49:08Z"
+  );
+  assert!(parser.parse("datetime.datetime(2024, 13, 1)").is_err());
+}
+
+#[test]
+fn test_input_formats() {
+  let now = Utc.from_utc_datetime(&tup_to_naive_date((2024, 3, 16, 12, 0, 0)));
+  let parser = Parser::new().now(now).input_format("%d.%m.%Y %H%M");
+  let iso = |parser: &Parser, input| parser.parse(input).unwrap().to_iso();
+
+  assert_eq!(iso(&parser, "10.04.2025 1312"), "2025-04-10T13:12:00Z");
+  assert_eq!(
+    parser.parse("10.04.2025 1312").unwrap().kind,
+    InputKind::Pattern
+  );
+  assert_eq!(
+    iso(&parser.clone().zone(Tz::Europe__Berlin), "10.04.2025 1312"),
+    "2025-04-10T11:12:00Z"
+  );
+
+  // Patterns replace the built-in formats and report each mismatch
+  let error =
+    |parser: &Parser, input| parser.parse(input).unwrap_err().to_string();
+  assert_eq!(
+    error(&parser, "tomorrow"),
+    "'tomorrow' matches no input format:\n\
+     '%d.%m.%Y %H%M' does not match: \
+     expected %d (day) at column 1, found 'tomorrow'"
+  );
+  assert!(error(&parser, "10.04.2025 13:12")
+    .ends_with("expected %M (minute) at column 14, found ':12'"));
+  assert!(error(&parser, "10.13.2025 1312")
+    .ends_with("%m (month) out of range at column 4"));
+  assert!(error(&parser, "10.04.2025")
+    .ends_with("expected %H (hour) at column 11, found end of input"));
+  assert!(error(&parser, "10.04.2025 1312 UTC")
+    .ends_with("unexpected ' UTC' at column 16 after the end of the pattern"));
+  assert!(error(&parser, "31.02.2025 1312").ends_with("no such date"));
+
+  // Tried in order
+  let parser = parser.input_format("%I:%M %p").input_format("%Y%j");
+  assert_eq!(iso(&parser, "3:15 PM"), "2024-03-16T15:15:00Z");
+  assert_eq!(iso(&parser, "2025100"), "2025-04-10T00:00:00Z");
+  assert_eq!(error(&parser, "x").lines().count(), 4);
+
+  let offsets = Parser::new().input_format("%d/%m/%Y %H:%M:%S%.f %z");
+  assert_eq!(
+    iso(&offsets, "10/04/2025 13:12:00.5 +0200"),
+    "2025-04-10T11:12:00.500Z"
+  );
+  assert!(error(&Parser::new().input_format("%Q"), "x")
+    .ends_with("unknown specifier '%Q'"));
+
+  // Reference times are read without them
+  assert_eq!(
+    iso(&parser.clone().without_input_formats(), "tomorrow"),
+    "2024-03-17T12:00:00Z"
+  );
+  let mut session = Session::new(parser).now(now);
+  assert_eq!(
+    session.eval(":now tomorrow").unwrap(),
+    "reference time: 2024-03-17T12:00:00Z"
+  );
+  assert!(session
+    .eval("10.04.2025 1312")
+    .unwrap()
+    .starts_with("= 2025-04-10T13:12:00Z"));
+}
+
+#[test]
+fn test_zone_queries() {
+  let winter = Utc.from_utc_datetime(&tup_to_naive_date(dt!(2027, 3, 1)));
+  let summer = Utc.from_utc_datetime(&tup_to_naive_date(dt!(2027, 7, 1)));
+
+  let berlin = ZoneInfo::at(Tz::Europe__Berlin, winter);
+  assert_eq!(berlin.offset.to_string(), "+01:00");
+  assert_eq!(berlin.abbreviation, "CET");
+  assert!(!berlin.dst);
+  assert_eq!(berlin.local.to_rfc3339(), "2027-03-01T01:00:00+01:00");
+  let berlin = ZoneInfo::at(Tz::Europe__Berlin, summer);
+  assert_eq!(berlin.abbreviation, "CEST");
+  assert!(berlin.dst);
+  // Zones without a common abbreviation are named by their offset
+  assert_eq!(
+    ZoneInfo::at(Tz::Asia__Kathmandu, winter).abbreviation,
+    "+0545"
+  );
+
+  let zones: Vec<_> = ZoneInfo::list("berlin", winter)
+    .into_iter()
+    .map(|info| info.zone)
+    .collect();
+  assert_eq!(zones, [Tz::Europe__Berlin]);
+  assert!(ZoneInfo::list("cest", summer)
+    .iter()
+    .any(|info| info.zone == Tz::Europe__Paris));
+  assert_eq!(ZoneInfo::find("europe/berlin"), Some(Tz::Europe__Berlin));
+  assert_eq!(ZoneInfo::find("Mars/Olympus_Mons"), None);
+
+  let ist = ZoneInfo::using_abbreviation("ist");
+  let offset_in = |zone| {
+    ist
+      .iter()
+      .find(|(candidate, _)| *candidate == zone)
+      .map(|(_, offset)| offset.to_string())
+  };
+  assert_eq!(offset_in(Tz::Asia__Kolkata).as_deref(), Some("+05:30"));
+  assert_eq!(offset_in(Tz::Europe__Dublin).as_deref(), Some("+01:00"));
+  assert_eq!(offset_in(Tz::Asia__Jerusalem).as_deref(), Some("+02:00"));
+  assert!(ZoneInfo::using_abbreviation("XYZ").is_empty());
+}
+
+#[test]
+fn test_ambiguous_abbreviations() {
+  let now = Utc.from_utc_datetime(&tup_to_naive_date(dt!(2024, 1, 1)));
+  let parser = Parser::new().now(now);
+  let iso = |parser: Parser, input| parser.parse(input).unwrap().to_iso();
+  let input = "2024-05-01 15:00 IST";
+
+  // Conventional meaning without a zone deciding
+  assert_eq!(iso(parser.clone(), input), "2024-05-01T09:30:00Z");
+  // The zone of the parser, then its region
+  assert_eq!(
+    iso(parser.clone().zone(Tz::Asia__Jerusalem), input),
+    "2024-05-01T13:00:00Z"
+  );
+  assert_eq!(
+    iso(parser.clone().zone(Tz::Europe__Berlin), input),
+    "2024-05-01T14:00:00Z"
+  );
+  assert_eq!(
+    iso(parser.clone().zone(Tz::Asia__Tokyo), "2024-05-01 15:00 CST"),
+    "2024-05-01T07:00:00Z"
+  );
+  assert_eq!(
+    iso(parser.clone(), "2024-05-01 15:00 CST"),
+    "2024-05-01T21:00:00Z"
+  );
+
+  // Candidates within the region conflict
+  let tokyo = parser.clone().zone(Tz::Asia__Tokyo);
+  let error = tokyo.parse(input).unwrap_err().to_string();
+  assert_eq!(
+    error,
+    "ambiguous zone abbreviation 'IST', could be \
+     +05:30 (Asia/Calcutta, Asia/Kolkata) or \
+     +02:00 (Asia/Jerusalem, Asia/Tel_Aviv); set a preferred zone to decide"
+  );
+  assert_eq!(
+    iso(tokyo.prefer_zone(Tz::Asia__Kolkata), input),
+    "2024-05-01T09:30:00Z"
+  );
+
+  // Offsets as they were at the date
+  assert_eq!(
+    iso(parser.clone(), "2012-06-01 12:00 MSK"),
+    "2012-06-01T08:00:00Z"
+  );
+  assert_eq!(
+    iso(parser.clone(), "2024-06-01 12:00 MSK"),
+    "2024-06-01T09:00:00Z"
+  );
+  assert_eq!(
+    iso(parser.clone(), "2024-06-15 12:00 CET"),
+    "2024-06-15T11:00:00Z"
+  );
+
+  let config = Config::parse("prefer_zones = ['Europe/Dublin']").unwrap();
+  assert_eq!(
+    iso(config.apply(parser.clone()).unwrap(), input),
+    "2024-05-01T14:00:00Z"
+  );
+}
+
+#[test]
+fn test_zones_in_expressions() {
+  // 17:00 in New York, the day before DST starts there
+  let now = Utc.from_utc_datetime(&tup_to_naive_date((2024, 3, 9, 22, 0, 0)));
+  let parser = Parser::new().now(now).zone(Tz::Europe__Berlin);
+  let iso = |input| parser.parse(input).unwrap().to_iso();
+
+  assert_eq!(iso("15:00 America/Sao_Paulo"), "2024-03-09T18:00:00Z");
+  assert_eq!(iso("3pm europe/paris"), "2024-03-09T14:00:00Z");
+  assert_eq!(iso("3pm PST"), "2024-03-09T23:00:00Z");
+  // Tomorrow there, at the offset it has then
+  assert_eq!(iso("tomorrow 9am in New York"), "2024-03-10T13:00:00Z");
+  assert_eq!(iso("tomorrow 9am in Tokyo"), "2024-03-11T00:00:00Z");
+  assert_eq!(iso("july 4 at 9am in boston"), "2024-07-04T13:00:00Z");
+  assert_eq!(iso("3pm in Rio de Janeiro"), "2024-03-09T18:00:00Z");
+  // Unknown places are an error
+  let error = |input| parser.parse(input).unwrap_err().to_string();
+  assert_eq!(
+    error("15:00 in Atlantis"),
+    "unknown time zone or city 'Atlantis'"
+  );
+  assert_eq!(error("9am in Tokio"), "unknown time zone or city 'Tokio'");
+}
+
+#[test]
+fn test_world_clock() {
+  let now = Utc.from_utc_datetime(&tup_to_naive_date((2024, 3, 1, 12, 0, 0)));
+  let parser = Parser::new().now(now).zone(Tz::America__Los_Angeles);
+  let config = Config::parse(
+    "show_zones = ['America/Los_Angeles', 'Europe/Berlin', 'Asia/Tokyo']",
+  )
+  .unwrap();
+  let zones = config.show_zones().unwrap();
+
+  let date = parser.parse("friday 4pm PST").unwrap();
+  let clock = date.world_clock(&zones);
+  assert_eq!(
+    clock.to_string(),
+    "America/Los_Angeles  Fri 2024-03-01 16:00  -08:00  PST\n\
+     Europe/Berlin        Sat 2024-03-02 01:00  +01:00  CET    +1 day\n\
+     Asia/Tokyo           Sat 2024-03-02 09:00  +09:00  JST    +1 day"
+  );
+
+  // Days as seen from the zone of the parser
+  let tokyo = parser.zone(Tz::Asia__Tokyo);
+  let date = tokyo.parse("2024-07-01 08:00").unwrap();
+  let clock = date.world_clock(&zones);
+  let days: Vec<i64> = clock.zones.iter().map(|time| time.day_change).collect();
+  assert_eq!(days, [-1, 0, 0]);
+  let json = serde_json::to_value(&clock).unwrap();
+  assert_eq!(json["utc"], "2024-06-30T23:00:00Z");
+  assert_eq!(json["zones"][1]["zone"], "Europe/Berlin");
+  assert_eq!(json["zones"][1]["local"], "2024-07-01T01:00:00+02:00");
+  assert_eq!(json["zones"][1]["abbreviation"], "CEST");
+  assert_eq!(json["zones"][1]["dst"], true);
+  assert_eq!(json["zones"][0]["day_change"], -1);
+
+  assert!(Config::parse("show_zones = ['Mars/Olympus']")
+    .unwrap()
+    .show_zones()
+    .is_err());
+}
+
+#[test]
+fn test_common_working_hours() {
+  // Wednesday, Europe is back on standard time from Sunday on, the US later
+  let now = Utc.from_utc_datetime(&tup_to_naive_date((2024, 10, 23, 12, 0, 0)));
+  let parser = Parser::new().now(now).zone(Tz::Europe__Berlin);
+
+  let week = parser.parse_range("next week").unwrap();
+  assert_eq!(
+    week.to_string(),
+    "2024-10-27T23:00:00Z/2024-11-03T23:00:00Z"
+  );
+  assert_eq!(
+    parser.parse_range("march").unwrap().to_string(),
+    "2024-02-29T23:00:00Z/2024-03-31T22:00:00Z"
+  );
+  assert_eq!(
+    parser.parse_range("next month").unwrap().to_string(),
+    "2024-10-31T23:00:00Z/2024-11-30T23:00:00Z"
+  );
+  assert_eq!(
+    parser.parse_range("last year").unwrap().to_string(),
+    "2022-12-31T23:00:00Z/2023-12-31T23:00:00Z"
+  );
+  assert!(parser.parse_range("tomorrow 4pm").is_err());
+  assert!(parser.parse_range("in 2 hours").is_err());
+  assert!(parser.parse_range("next hour").is_err());
+
+  let zones = [Tz::Europe__Berlin, Tz::America__New_York];
+  let hours: WorkingHours = "9-17".parse().unwrap();
+  let slots = common_working_hours(week, &zones, hours).unwrap();
+  let slots: Vec<String> = slots.iter().map(|slot| slot.to_string()).collect();
+  assert_eq!(
+    slots,
+    [
+      // one hour more while New York still has DST
+      "2024-10-28T13:00:00Z/2024-10-28T16:00:00Z",
+      "2024-10-29T13:00:00Z/2024-10-29T16:00:00Z",
+      "2024-10-30T13:00:00Z/2024-10-30T16:00:00Z",
+      "2024-10-31T13:00:00Z/2024-10-31T16:00:00Z",
+      "2024-11-01T13:00:00Z/2024-11-01T16:00:00Z",
+    ]
+  );
+
+  let zones = [Tz::Europe__Berlin, Tz::America__New_York, Tz::Asia__Tokyo];
+  assert!(common_working_hours(week, &zones, hours)
+    .unwrap()
+    .is_empty());
+  // A night shift in Tokyo
+  let night: WorkingHours = "22:00-06:00".parse().unwrap();
+  let tomorrow = parser.parse_range("tomorrow").unwrap();
+  let slots =
+    common_working_hours(tomorrow, &[Tz::Asia__Tokyo], night).unwrap();
+  assert_eq!(slots.len(), 1);
+  assert_eq!(
+    slots[0].to_string(),
+    "2024-10-24T13:00:00Z/2024-10-24T21:00:00Z"
+  );
+
+  assert!("9".parse::<WorkingHours>().is_err());
+  assert!(common_working_hours(week, &[], hours).is_err());
+}
+
+#[test]
+fn test_convert() {
+  let now = Utc.from_utc_datetime(&tup_to_naive_date((2024, 5, 1, 12, 0, 0)));
+  let parser = Parser::new().now(now).zone(Tz::America__New_York);
+  let tokyo = ZoneInfo::find("tokyo").unwrap();
+  assert_eq!(tokyo, Tz::Asia__Tokyo);
+  assert_eq!(
+    ZoneInfo::find("america/new_york"),
+    Some(Tz::America__New_York)
+  );
+
+  let convert = |input, zone| {
+    parser
+      .parse(input)
+      .unwrap()
+      .in_zone(zone)
+      .format(Format::Local)
+  };
+  assert_eq!(
+    convert("2024-05-01T12:00:00Z", tokyo),
+    "2024-05-01T21:00:00+09:00"
+  );
+  assert_eq!(convert("@1700000000", tokyo), "2023-11-15T07:13:20+09:00");
+  // From the zone of the parser to another one
+  assert_eq!(
+    convert("tomorrow 9am", Tz::Europe__Berlin),
+    "2024-05-02T15:00:00+02:00"
+  );
+  let converted = parser.parse("9am").unwrap().in_zone(tokyo);
+  assert_eq!(converted.to_iso(), "2024-05-01T13:00:00Z");
+}
+
+#[test]
+fn test_interactive_session() {
+  let history = std::env::temp_dir()
+    .join(format!("tu-history-{}", std::process::id()))
+    .join("history");
+  let _ = std::fs::remove_file(&history);
+  let mut session = Session::new(Parser::new()).history_file(history.clone());
+  let mut eval = |line| session.eval(line).unwrap();
+
+  assert_eq!(
+    eval(":now 2024-03-09 12:00"),
+    "reference time: 2024-03-09T12:00:00Z"
+  );
+  let result = eval("tomorrow 9am");
+  assert!(result.starts_with("= 2024-03-10T09:00:00Z\n  Natural\n"));
+  assert!(result.contains("2. added 1 × 1 days -> 2024-03-10T12:00:00+00:00"));
+  assert_eq!(eval(":explain"), "explanation: off");
+  assert_eq!(eval(":zone tokyo"), "zone: Asia/Tokyo");
+  assert_eq!(
+    eval("!2"),
+    "tomorrow 9am\n= 2024-03-10T00:00:00Z\n  2024-03-10T09:00:00+09:00"
+  );
+  assert_eq!(
+    eval("bogus thing"),
+    "error: expected week day or month name"
+  );
+  assert_eq!(eval("!99"), "error: no history entry '!99'");
+  assert_eq!(eval(":foo"), "error: unknown command ':foo', see :help");
+  assert_eq!(session.eval(":quit"), None);
+
+  // The next session continues the history
+  let session = Session::new(Parser::new()).history_file(history.clone());
+  assert_eq!(
+    session.history(),
+    [
+      ":now 2024-03-09 12:00",
+      "tomorrow 9am",
+      ":explain",
+      ":zone tokyo",
+      "tomorrow 9am",
+      "bogus thing",
+      ":foo",
+    ]
+  );
+  let _ = std::fs::remove_dir_all(history.parent().unwrap());
+}
+
+#[test]
+fn test_pinned_reference_time() {
+  let clock = Utc.from_utc_datetime(&tup_to_naive_date((2024, 3, 9, 12, 0, 0)));
+  let config = Config::parse("[dates]\nkickoff = 'next monday 10:00'").unwrap();
+  let parser = config.apply(Parser::new().now(clock)).unwrap();
+
+  // Chained like --now "last monday 09:00" --now "in 2 hours"
+  let mut pinned = parser.clone();
+  for expression in ["last monday 09:00", "in 2 hours"] {
+    let now = pinned.parse(expression).unwrap().utc;
+    pinned = pinned.now(now);
+  }
+  assert_eq!(to_iso(pinned.reference_time()), "2024-03-04T11:00:00Z");
+  assert_eq!(
+    pinned.parse("tomorrow").unwrap().to_iso(),
+    "2024-03-05T11:00:00Z"
+  );
+
+  // Dates of the config follow the pinned time
+  let earlier = clock - chrono::Duration::weeks(2);
+  assert_eq!(
+    parser.parse("kickoff").unwrap().to_iso(),
+    "2024-03-11T10:00:00Z"
+  );
+  let earlier = config.apply_dates(parser.now(earlier)).unwrap();
+  assert_eq!(
+    earlier.parse("kickoff").unwrap().to_iso(),
+    "2024-02-26T10:00:00Z"
+  );
+
+  let mut session = Session::new(Parser::new()).now(clock);
+  assert!(session
+    .eval("tomorrow")
+    .unwrap()
+    .starts_with("= 2024-03-10T12:00:00Z"));
+}
+
+#[test]
+fn test_durations_and_vocabulary() {
+  let clock =
+    Utc.from_utc_datetime(&tup_to_naive_date((2024, 2, 24, 12, 0, 0)));
+  let parser = Parser::new().now(clock);
+
+  let duration = parser.duration("-2d").unwrap();
+  assert_eq!(iso_duration(duration), "-P2D");
+  assert_eq!(humanize(duration), "2 days ago");
+  let duration = parser.diff("monday 9am", "friday 17:30").unwrap();
+  assert_eq!(iso_duration(duration), "P4DT8H30M");
+  assert_eq!(
+    iso_duration(chrono::Duration::milliseconds(-500)),
+    "-PT0.5S"
+  );
+  assert_eq!(iso_duration(chrono::Duration::zero()), "PT0S");
+  assert_eq!(
+    humanize(parser.duration("in 3 weeks").unwrap()),
+    "in 3 weeks"
+  );
+  assert_eq!(humanize(chrono::Duration::zero()), "now");
+
+  use language::Language;
+  let vocabulary = language::ENGLISH.vocabulary();
+  assert!(vocabulary.contains(&"friday"));
+  assert!(vocabulary.contains(&"september"));
+  assert!(language::by_code("de")
+    .unwrap()
+    .vocabulary()
+    .contains(&"morgen"));
+}
+
+#[test]
+fn test_rounding() {
+  let clock =
+    Utc.from_utc_datetime(&tup_to_naive_date((2024, 3, 16, 12, 56, 41)));
+  let parser = Parser::new().now(clock);
+  let rounded = |rounding, unit, input| {
+    parser
+      .clone()
+      .rounding(rounding, unit)
+      .parse(input)
+      .unwrap()
+      .to_iso()
+  };
+
+  assert_eq!(
+    rounded(Rounding::Floor, TimeUnit::Day, "tomorrow"),
+    "2024-03-17T00:00:00Z"
+  );
+  assert_eq!(
+    rounded(Rounding::Ceil, TimeUnit::Week, "today"),
+    "2024-03-18T00:00:00Z"
+  );
+  assert_eq!(
+    rounded(Rounding::Round, TimeUnit::Hour, "today"),
+    "2024-03-16T13:00:00Z"
+  );
+  assert_eq!(
+    rounded(Rounding::Round, TimeUnit::Minute, "today"),
+    "2024-03-16T12:57:00Z"
+  );
+  assert_eq!(
+    rounded(Rounding::Floor, TimeUnit::Year, "today"),
+    "2024-01-01T00:00:00Z"
+  );
+  // Already at a boundary
+  assert_eq!(
+    rounded(Rounding::Ceil, TimeUnit::Day, "2024-03-20T00:00:00Z"),
+    "2024-03-20T00:00:00Z"
+  );
+
+  // In the zone of the parser, across the switch to summer time
+  let berlin = parser
+    .clone()
+    .zone(Tz::Europe__Berlin)
+    .rounding(Rounding::Ceil, TimeUnit::Day);
+  assert_eq!(
+    berlin.parse("2024-03-31T12:00:00Z").unwrap().to_iso(),
+    "2024-03-31T22:00:00Z"
+  );
+  let sunday = parser.clone().week_start(WeekStart::Sunday);
+  assert_eq!(
+    sunday
+      .rounding(Rounding::Floor, TimeUnit::Week)
+      .parse("today")
+      .unwrap()
+      .to_iso(),
+    "2024-03-10T00:00:00Z"
+  );
+
+  assert_eq!(
+    parser.parse("start of tomorrow").unwrap().to_iso(),
+    "2024-03-17T00:00:00Z"
+  );
+  assert_eq!(
+    parser.parse("Start of the month").unwrap().to_iso(),
+    "2024-03-01T00:00:00Z"
+  );
+  assert_eq!(
+    parser.parse("start of next week").unwrap().to_iso(),
+    "2024-03-18T00:00:00Z"
+  );
+  assert_eq!(
+    parser.parse("start of the hour").unwrap().to_iso(),
+    "2024-03-16T12:00:00Z"
+  );
+  assert_eq!(
+    parser.parse("start of friday").unwrap().to_iso(),
+    "2024-03-22T00:00:00Z"
+  );
+  assert_eq!(
+    parser
+      .clone()
+      .time_policy(TimePolicy::Preserve)
+      .parse("start of next week")
+      .unwrap()
+      .to_iso(),
+    "2024-03-18T00:00:00Z"
+  );
+  assert_eq!(
+    parser.parse("start of next month").unwrap().to_iso(),
+    "2024-04-01T00:00:00Z"
+  );
+  assert_eq!(
+    parser.parse("start of next year").unwrap().to_iso(),
+    "2025-01-01T00:00:00Z"
+  );
+  assert_eq!(
+    parser.parse("last month").unwrap().to_iso(),
+    "2024-02-01T00:00:00Z"
+  );
+  assert!(parser.parse("start of 4pm").is_err());
+  assert!(parser.parse("start of 2 hours").is_err());
+  assert!(parser.parse("start of tomorrow 9am").is_err());
+  assert!("fortnight".parse::<TimeUnit>().is_err());
+}
+
+#[test]
+fn test_time_policy() {
+  let clock =
+    Utc.from_utc_datetime(&tup_to_naive_date((2024, 3, 16, 12, 56, 41)));
+  let parser = Parser::new().now(clock);
+  let parse = |parser: &Parser, input| parser.parse(input).unwrap().to_iso();
+
+  // Relative dates keep the time, named and absolute dates start at midnight
+  assert_eq!(parse(&parser, "1 week"), "2024-03-23T12:56:41Z");
+  assert_eq!(parse(&parser, "1 month"), "2024-04-16T12:56:41Z");
+  assert_eq!(parse(&parser, "friday"), "2024-03-22T00:00:00Z");
+  assert_eq!(parse(&parser, "2024-05-01"), "2024-05-01T00:00:00Z");
+
+  let preserve = parser.clone().time_policy(TimePolicy::Preserve);
+  for (input, expected) in [
+    ("tomorrow", "2024-03-17T12:56:41Z"),
+    ("1 month", "2024-04-16T12:56:41Z"),
+    ("friday", "2024-03-22T12:56:41Z"),
+    ("march", "2024-03-01T12:56:41Z"),
+    ("2024-05-01", "2024-05-01T12:56:41Z"),
+    ("next week", "2024-03-18T12:56:41Z"),
+    ("tomorrow 9am", "2024-03-17T09:00:00Z"),
+  ] {
+    assert_eq!(parse(&preserve, input), expected, "preserve: {input}");
+  }
+
+  let start_of_day = parser.clone().time_policy(TimePolicy::StartOfDay);
+  for (input, expected) in [
+    ("tomorrow", "2024-03-17T00:00:00Z"),
+    ("1 week", "2024-03-23T00:00:00Z"),
+    ("1 month", "2024-04-16T00:00:00Z"),
+    ("3 days ago", "2024-03-13T00:00:00Z"),
+    ("friday", "2024-03-22T00:00:00Z"),
+    ("2 hours", "2024-03-16T14:56:41Z"),
+  ] {
+    assert_eq!(
+      parse(&start_of_day, input),
+      expected,
+      "start of day: {input}"
+    );
+  }
+
+  // Wall clock time across the switch to summer time
+  let berlin = preserve.zone(Tz::Europe__Berlin);
+  assert_eq!(
+    berlin.parse("2024-03-31").unwrap().local.to_rfc3339(),
+    "2024-03-31T13:56:41+02:00"
+  );
+
+  let config = Config::parse("time_policy = 'start-of-day'").unwrap();
+  let parser = config.apply(parser).unwrap();
+  assert_eq!(parse(&parser, "tomorrow"), "2024-03-17T00:00:00Z");
+  assert!("noon".parse::<TimePolicy>().is_err());
+}
+
+#[test]
+fn test_bias() {
+  // Saturday
+  let clock =
+    Utc.from_utc_datetime(&tup_to_naive_date((2024, 3, 16, 12, 56, 41)));
+  let parser = Parser::new().now(clock);
+  let cases = [
+    // input, past, future, nearest
+    ("friday", "2024-03-15", "2024-03-22", "2024-03-15"),
+    ("saturday", "2024-03-16", "2024-03-16", "2024-03-16"),
+    ("sunday", "2024-03-10", "2024-03-17", "2024-03-17"),
+    ("march", "2024-03-01", "2024-03-01", "2024-03-01"),
+    ("february", "2024-02-01", "2025-02-01", "2024-02-01"),
+    ("december", "2023-12-01", "2024-12-01", "2023-12-01"),
+    ("5 may", "2023-05-05", "2024-05-05", "2024-05-05"),
+    ("feb 29", "2024-02-29", "2028-02-29", "2024-02-29"),
+    // explicit directions aren't affected
+    ("next friday", "2024-03-22", "2024-03-22", "2024-03-22"),
+  ];
+  for (input, past, future, nearest) in cases {
+    for (bias, expected) in [
+      (Bias::Past, past),
+      (Bias::Future, future),
+      (Bias::Nearest, nearest),
+    ] {
+      let date = parser.clone().bias(bias).parse(input).unwrap();
+      assert_eq!(
+        date.utc.date_naive().to_string(),
+        expected,
+        "{input} with {bias:?}"
+      );
+    }
+  }
+
+  // With a time, instants count instead of whole days
+  let past = parser.clone().bias(Bias::Past);
+  assert_eq!(
+    past.parse("saturday 5pm").unwrap().to_iso(),
+    "2024-03-09T17:00:00Z"
+  );
+
+  // Dates without a year in logs and input formats
+  assert_eq!(
+    past.parse("Mar 17 10:00:00").unwrap().to_iso(),
+    "2023-03-17T10:00:00Z"
+  );
+  let future = parser.clone().bias(Bias::Future).input_format("%d.%m");
+  assert_eq!(
+    future.parse("20.01").unwrap().to_iso(),
+    "2025-01-20T00:00:00Z"
+  );
+  assert_eq!(
+    future.parse("16.03").unwrap().to_iso(),
+    "2024-03-16T00:00:00Z"
+  );
+
+  let config = Config::parse("prefer = 'past'").unwrap();
+  let parser = config.apply(parser).unwrap();
+  assert_eq!(
+    parser.parse("friday").unwrap().to_iso(),
+    "2024-03-15T00:00:00Z"
+  );
+  assert!("recent".parse::<Bias>().is_err());
+}
+
+// runs the tu binary at a pinned reference time, without a config file
+fn run_tu(args: &[&str]) -> Result<String, String> {
+  let output = std::process::Command::new(env!("CARGO_BIN_EXE_tu"))
+    .args(args)
+    .env("TU_NOW", "2024-03-16T12:56:41Z")
+    .env("XDG_CONFIG_HOME", std::env::temp_dir().join("tu-no-config"))
+    .output()
+    .unwrap();
+  if output.status.success() {
+    Ok(String::from_utf8(output.stdout).unwrap())
+  }
+  else {
+    Err(String::from_utf8(output.stderr).unwrap())
+  }
+}
+
+#[test]
+fn test_global_options_before_subcommands() {
+  assert_eq!(
+    run_tu(&["--zone", "Europe/Berlin", "range", "next", "week"]),
+    Ok("2024-03-17T23:00:00Z/2024-03-24T23:00:00Z\n".to_string())
+  );
+  assert_eq!(
+    run_tu(&["--format", "unix", "duration", "tomorrow"]),
+    Ok("P1D\n".to_string())
+  );
+  assert_eq!(
+    run_tu(&["--zone", "America/New_York", "convert", "--to", "Berlin"])
+      .map_err(|e| e.contains("<EXPRESSION>")),
+    Err(true)
+  );
+  assert!(run_tu(&["--lang", "de", "completions", "fish"])
+    .unwrap()
+    .contains("morgen"));
+  assert_eq!(
+    run_tu(&["--zone", "Europe/Berlin", "friday"]),
+    Ok("2024-03-21T23:00:00Z".to_string())
+  );
+}
+
+#[test]
+fn test_convert_to_after_expression() {
+  assert_eq!(
+    run_tu(&["convert", "2024-05-01T12:00:00Z", "--to", "Asia/Tokyo"]),
+    Ok("2024-05-01T21:00:00+09:00\n".to_string())
+  );
+  assert_eq!(
+    run_tu(&["convert", "2024-05-01T12:00:00Z", "--to=Tokyo"]),
+    Ok("2024-05-01T21:00:00+09:00\n".to_string())
+  );
+  assert_eq!(
+    run_tu(&["convert", "--to", "Tokyo", "2024-05-01T12:00:00Z"]),
+    Ok("2024-05-01T21:00:00+09:00\n".to_string())
+  );
+  assert_eq!(
+    run_tu(&["convert", "2024-05-01T12:00:00Z"])
+      .map_err(|e| e.contains("--to") && e.contains("Usage: tu convert")),
+    Err(true)
+  );
+  assert_eq!(
+    run_tu(&["convert", "2024-05-01T12:00:00Z", "--to"])
+      .map_err(|e| e.contains("Usage: tu convert")),
+    Err(true)
+  );
+}